@@ -0,0 +1,85 @@
+use core::{alloc::LayoutError, error::Error, fmt};
+
+/// Error returned by `init_unsized_checked` of structs with `#[dstify(max_len = ...)]` or `#[dstify(len_field = ...)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitError {
+    /// the struct would exceed `isize::MAX` bytes
+    Layout(LayoutError),
+    /// the last field holds more than `max_len` elements, or more than the `len_field` named by `len_field` can count
+    TooLong {
+        len: usize,
+        max_len: usize,
+        len_field: Option<&'static str>,
+    },
+}
+
+impl From<LayoutError> for InitError {
+    fn from(err: LayoutError) -> Self {
+        InitError::Layout(err)
+    }
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Layout(err) => fmt::Display::fmt(err, f),
+            InitError::TooLong {
+                len,
+                max_len,
+                len_field: None,
+            } => write!(
+                f,
+                "length {len} of the last field exceeds `max_len` {max_len}"
+            ),
+            InitError::TooLong {
+                len,
+                max_len,
+                len_field: Some(len_field),
+            } => write!(
+                f,
+                "length {len} of the last field exceeds {max_len}, the most the length field `{len_field}` can hold"
+            ),
+        }
+    }
+}
+
+impl Error for InitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InitError::Layout(err) => Some(err),
+            InitError::TooLong { .. } => None,
+        }
+    }
+}
+
+/// Error returned by `ref_from_bytes` and `mut_from_bytes` of structs with `#[dstify(pod)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromBytesError {
+    /// the bytes aren't aligned to the alignment of the struct
+    Misaligned { align: usize },
+    /// the bytes can't hold the normal fields followed by whole elements of the last field, without trailing bytes
+    Size { len: usize },
+    /// the last field would hold more than `max_len` elements
+    TooLong { len: usize, max_len: usize },
+}
+
+impl fmt::Display for FromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromBytesError::Misaligned { align } => {
+                write!(f, "bytes are not aligned to {align}")
+            }
+            FromBytesError::Size { len } => {
+                write!(f, "{len} bytes do not match the size of any instance")
+            }
+            FromBytesError::TooLong { len, max_len } => {
+                write!(
+                    f,
+                    "length {len} of the last field exceeds `max_len` {max_len}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for FromBytesError {}
@@ -0,0 +1,37 @@
+//! Helpers for sharing DSTs with C code.
+
+use std::io::{self, Write};
+
+/// Writes a C header file containing `declarations`, e.g. the `C_DECLARATION`s generated by `#[dstify(c_declaration)]`,
+/// wrapped in an include guard named `guard`.
+///
+/// ```
+/// use dstify::Dstify;
+///
+/// #[derive(Dstify)]
+/// #[dstify(c_declaration)]
+/// #[repr(C)]
+/// struct Msg {
+///     len: u32,
+///     data: [u8],
+/// }
+///
+/// let mut header = Vec::new();
+/// dstify::ffi::write_header(&mut header, "MSG_H", &[Msg::C_DECLARATION]).unwrap();
+/// assert!(String::from_utf8(header).unwrap().contains("struct Msg {\n    uint32_t len;\n    uint8_t data[];\n};\n"));
+/// ```
+pub fn write_header<W: Write>(mut out: W, guard: &str, declarations: &[&str]) -> io::Result<()> {
+    writeln!(out, "#ifndef {guard}")?;
+    writeln!(out, "#define {guard}")?;
+    writeln!(out)?;
+    writeln!(out, "#include <stdbool.h>")?;
+    writeln!(out, "#include <stddef.h>")?;
+    writeln!(out, "#include <stdint.h>")?;
+    for declaration in declarations {
+        writeln!(out)?;
+        write!(out, "{declaration}")?;
+    }
+    writeln!(out)?;
+    writeln!(out, "#endif /* {guard} */")?;
+    out.flush()
+}
@@ -74,7 +74,7 @@
 //!  - for `slice` DST: a reference
 //!  - for `dyn Trait` DST: an owned value
 //!
-//! The return type `R`, determines the smart pointer type that should be constructed. The bounding trait - [`SmartPointer`], is implemented for [`Box`](`alloc::boxed::Box`), [`Rc`](`alloc::rc::Rc`) and [`Arc`](`alloc::sync::Arc`).
+//! The return type `R`, determines the smart pointer type that should be constructed. The bounding trait - [`SmartPointer`], is implemented for [`Box`](`alloc::boxed::Box`), [`Rc`](`alloc::rc::Rc`), [`Arc`](`alloc::sync::Arc`), their [pinned](`core::pin::Pin`) versions `Pin<Box<T>>`, `Pin<Rc<T>>` and `Pin<Arc<T>>` for `!Unpin` values, the thin counterparts [`ThinBox`], [`ThinRc`] and [`ThinArc`], which are one pointer wide, keeping the slice length or vtable in the allocation, [`UniqueRc`] and [`UniqueArc`], which allow mutation before turning into `Rc`/`Arc` without copying, [`InlineDst`], which avoids the allocation for small values, and [`NonNull`](core::ptr::NonNull) for FFI, freed by [`dealloc`].
 //!
 //! The `checked` method returns `LayoutError` if the size of the resulting instance would exceed `isize::MAX` bytes.
 //! The "unchecked" method panics in that case.
@@ -99,6 +99,32 @@
 //!     }
 //! }
 //! ```
+//! For `slice` DSTs, `#[dstify(raw_parts)]` generates `into_raw_parts` and `from_raw_parts` as well, converting `Box<Self>`
+//! to and from its thin base address and the length of the last field, e.g. to pass it as an opaque handle across FFI boundaries.
+//! ```
+//! use dstify::Dstify;
+//! use std::ptr::NonNull;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(raw_parts)]
+//! #[repr(C)]
+//! struct Message {
+//!     id: u32,
+//!     payload: [u8],
+//! }
+//!
+//! let message: Box<Message> = Message::init_unsized(1, &[1, 2, 3]);
+//! let (ptr, len): (NonNull<u8>, usize) = Message::into_raw_parts(message);
+//! assert_eq!(len, 3);
+//! // SAFETY: `ptr` and `len` come from `into_raw_parts`
+//! let message = unsafe { Message::from_raw_parts(ptr, len) };
+//! assert_eq!((message.id, &message.payload), (1, &[1, 2, 3][..]));
+//!
+//! let message: NonNull<Message> = Message::init_unsized(2, &[]);
+//! // SAFETY: the pointer comes from `init_unsized` and isn't used afterwards
+//! unsafe { dstify::dealloc(message) };
+//! ```
+//!
 //! ## Requirements
 //! The type must be a `struct`. `enums` and `unions` are not supported as it's forbidden to define a dynamically-sized `enum` or `union` in current rust.
 //! It must be annotated with `#[repr(C)]` and the last field *must* be a DST.
@@ -119,17 +145,553 @@
 //! }
 //! ```
 //!
+//! ## Attributes
+//!
+//! Additional code can be generated by annotating the struct with `#[dstify(...)]`.
+//!
+//! ### `borrow_tail`
+//! Implements [`Borrow<Tail>`](core::borrow::Borrow) for `Box<Self>` together with `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`
+//! that only take the last field into account, so boxed values can be looked up in maps and sets by their tail.
+//! Only available for `slice` DSTs.
+//! `Rc<Self>` and `Arc<Self>` cannot implement `Borrow<Tail>` due to orphan rules.
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use dstify::Dstify;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(borrow_tail)]
+//! #[repr(C)]
+//! struct Symbol {
+//!     id: u32,
+//!     name: str,
+//! }
+//!
+//! let mut symbols = HashMap::<Box<Symbol>, u32>::new();
+//! symbols.insert(Symbol::init_unsized(1, "main"), 0x1000);
+//! assert_eq!(symbols.get("main"), Some(&0x1000));
+//! # }
+//! ```
+//!
+//! ### `deref`
+//! Implements [`Deref<Target = Tail>`](core::ops::Deref) and [`AsRef<U>`](core::convert::AsRef) for every `U` the last field implements `AsRef<U>` for,
+//! e.g. `AsRef<str>`, `AsRef<[u8]>`, `AsRef<OsStr>` and `AsRef<Path>` when the last field is `str`.
+//! ```
+//! use dstify::Dstify;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(deref)]
+//! #[repr(C)]
+//! struct Tagged {
+//!     tag: u8,
+//!     text: str,
+//! }
+//!
+//! let tagged: Box<Tagged> = Tagged::init_unsized(1, "text");
+//! assert!(tagged.starts_with("te"));
+//! assert_eq!(AsRef::<[u8]>::as_ref(&*tagged), b"text");
+//! ```
+//!
+//! ### `forward(...)`
+//! Implements the listed traits by delegating to the last field.
+//! Supported traits are `Debug`, `Display`, `Error`, `Iterator`, `io::Read`, `io::Write` and `Future`.
+//! `Display` optionally accepts a format string written before the last field, which may refer to the other fields by name
+//! (`f0`, `f1`, ... for tuple structs).
+//! ```
+//! use dstify::Dstify;
+//! use std::error::Error;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(forward(Debug, Display = "line {line}: ", Error))]
+//! #[repr(C)]
+//! struct Context {
+//!     line: u64,
+//!     err: dyn Error,
+//! }
+//!
+//! let err: Box<Context> = Context::init_unsized(3, std::fmt::Error);
+//! assert_eq!(err.to_string(), "line 3: an error occurred when formatting an argument");
+//! ```
+//!
+//! ### `tail_traits(...)`
+//! `dyn Trait` can only name a single non-auto trait. This attribute defines the trait named in the last field (`dyn DebugAny` below)
+//! as a combination of the listed traits, implemented for every type implementing all of them,
+//! so `init_unsized` accepts any such value.
+//! For every listed trait `Trait`, accessors `as_trait` and `as_trait_mut` returning `&dyn Trait` and `&mut dyn Trait` are generated.
+//! Listing `Any` additionally generates `downcast_ref` and `downcast_mut`, unless [`downcast`](#downcast) is used too.
+//! ```
+//! use dstify::Dstify;
+//! use std::{any::Any, fmt::Debug};
+//!
+//! #[derive(Dstify)]
+//! #[dstify(tail_traits(Debug, Any))]
+//! #[repr(C)]
+//! struct Event {
+//!     id: u64,
+//!     payload: dyn DebugAny,
+//! }
+//!
+//! let event: Box<Event> = Event::init_unsized(1, 42u32);
+//! assert_eq!(format!("{:?}", event.as_debug()), "42");
+//! assert_eq!(event.downcast_ref::<u32>(), Some(&42));
+//! ```
+//!
+//! ### `sized_twin = "Name"`
+//! Generates a `#[repr(C)]` struct `Name<T>` with the same fields as the annotated struct, except for the last one, which becomes `T`.
+//! The twin has the same layout as the DST whose last field holds a `T`,
+//! so `Box<Name<[T; N]>>` (for `[T]` last field) and `Box<Name<D>>` (for `dyn Trait` last field) convert into `Box<Self>` via `From`
+//! without another allocation. This allows building the value on the stack or in a `const` first.
+//! If some lifetime or type parameters of the struct are only used by the last field, the twin ends with
+//! a `_marker: PhantomData` field using them.
+//! ```
+//! use dstify::Dstify;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(sized_twin = "TableOf")]
+//! #[repr(C)]
+//! struct Table {
+//!     id: u8,
+//!     entries: [u32],
+//! }
+//!
+//! const DEFAULT: TableOf<[u32; 3]> = TableOf { id: 0, entries: [1, 2, 3] };
+//! let table: Box<Table> = Box::new(DEFAULT).into();
+//! assert_eq!(table.entries, [1, 2, 3]);
+//! ```
+//!
+//! ### `downcast`
+//! Requires `sized_twin` and a `dyn Trait` last field, where `Trait` is `Any` or has `Any` as a supertrait.
+//! Generates `downcast`, `downcast_ref` and `downcast_mut`, that check the type of the last field
+//! and return the sized twin, like [`Box<dyn Any>::downcast`](alloc::boxed::Box::downcast) does.
+//! `downcast` reuses the allocation.
+//! ```
+//! use dstify::Dstify;
+//! use std::any::Any;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(sized_twin = "EventOf", downcast)]
+//! #[repr(C)]
+//! struct Event {
+//!     id: u64,
+//!     payload: dyn Any,
+//! }
+//!
+//! let event: Box<Event> = Event::init_unsized(1, String::from("payload"));
+//! assert!(event.downcast_ref::<u32>().is_none());
+//! let event: Box<EventOf<String>> = event.downcast().ok().unwrap();
+//! assert_eq!(event.payload, "payload");
+//! ```
+//!
+//! ### `with_tail` and `map_tail`
+//! `with_tail` generates a method constructing a new instance from clones of the other fields and a new last field.
+//! `map_tail` generates a function consuming `Box<Self>`, moving the other fields into a new instance,
+//! whose last field is computed from the old one.
+//! ```
+//! use dstify::Dstify;
+//! use std::rc::Rc;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(with_tail, map_tail)]
+//! #[repr(C)]
+//! struct Document {
+//!     id: u64,
+//!     text: str,
+//! }
+//!
+//! let doc: Box<Document> = Document::init_unsized(1, "text");
+//! let other: Rc<Document> = doc.with_tail("other");
+//! let upper: Box<Document> = Document::map_tail(doc, |text| text.to_uppercase());
+//! assert_eq!((other.id, &other.text), (1, "other"));
+//! assert_eq!((upper.id, &upper.text), (1, "TEXT"));
+//! ```
+//!
+//! ### `make_mut`
+//! For `slice` DSTs, generates `make_mut`, which works like [`Rc::make_mut`](alloc::rc::Rc::make_mut) for any [`MakeMut`] pointer.
+//! When the pointer is shared, the other fields are cloned and the last field copied into a fresh allocation first.
+//! ```
+//! use dstify::Dstify;
+//! use std::sync::Arc;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(make_mut)]
+//! #[repr(C)]
+//! struct Config {
+//!     version: u64,
+//!     data: [u8],
+//! }
+//!
+//! let mut config: Arc<Config> = Config::init_unsized(1, &[1, 2]);
+//! let shared = config.clone();
+//! Config::make_mut(&mut config).version += 1;
+//! assert_eq!((shared.version, config.version), (1, 2));
+//! assert_eq!(config.data, shared.data);
+//! ```
+//!
+//! ### `resize_tail`
+//! For `[T]` last field, generates `extend_tail`, `truncate_tail` and `resize_tail_with`,
+//! which change the length of the last field of a `Box<Self>` in place, using `realloc`. With `max_len`, `extend_tail`
+//! and `resize_tail_with` return [`InitError::TooLong`] instead of growing the last field past it.
+//! ```
+//! use dstify::Dstify;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(resize_tail)]
+//! #[repr(C)]
+//! struct Samples {
+//!     rate: u32,
+//!     samples: [i16],
+//! }
+//!
+//! let mut samples: Box<Samples> = Samples::init_unsized(44100, &[1, 2]);
+//! Samples::extend_tail(&mut samples, &[3, 4]);
+//! Samples::truncate_tail(&mut samples, 3);
+//! Samples::resize_tail_with(&mut samples, 4, || 0);
+//! assert_eq!(samples.samples, [1, 2, 3, 0]);
+//! ```
+//!
+//! ### `buffer = "Name"`
+//! For `[T]` last field, generates a growable companion struct `Name`, which owns the other fields and the elements of the last field
+//! with spare capacity, like a `Vec`. It supports `push`, `extend_from_slice`, `reserve`, `shrink_to_fit` and [`Extend`],
+//! dereferences to the DST and freezes into it with `into_box`, `into_rc` or `into_arc`.
+//! `new` and `with_capacity` allocate like `Box`, `with_capacity_in::<R>` like `R`, being `Box`, `Rc` or `Arc`. Freezing into
+//! the pointer the buffer was allocated like reuses the allocation, once shrunk to fit, which `Rc` and `Arc` can only
+//! do by copying, so their buffers are best allocated with the final capacity. Freezing into other pointers copies.
+//! ```
+//! use dstify::Dstify;
+//! use std::sync::Arc;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(buffer = "AdjacencyBuf")]
+//! #[repr(C)]
+//! struct Adjacency {
+//!     node: u32,
+//!     neighbors: [u32],
+//! }
+//!
+//! let mut buf = AdjacencyBuf::new(1);
+//! buf.push(2);
+//! buf.extend([3, 4]);
+//! let adjacency: Box<Adjacency> = buf.into_box();
+//! assert_eq!(adjacency.neighbors, [2, 3, 4]);
+//!
+//! let mut buf = AdjacencyBuf::with_capacity_in::<Arc<_>>(2, 2);
+//! buf.extend([1, 3]);
+//! let adjacency: Arc<Adjacency> = buf.into_arc(); // doesn't copy
+//! assert_eq!(adjacency.neighbors, [1, 3]);
+//! ```
+//!
+//! ### `max_len = N`
+//! For `slice` DSTs, limits the last field to `N` elements, so that [`ThinBox`], [`ThinRc`] and [`ThinArc`] store its length
+//! as the smallest of `u8`, `u16` and `u32` fitting `N` instead of `usize`. `init_unsized` panics when the limit is exceeded,
+//! while `init_unsized_checked` returns [`InitError::TooLong`], before allocating. The other constructors check the limit
+//! up front as well, see `pod` and `resize_tail`.
+//! ```
+//! use dstify::{Dstify, InitError, ThinBox};
+//!
+//! #[derive(Dstify)]
+//! #[dstify(max_len = 255)]
+//! #[repr(C)]
+//! struct Label {
+//!     text: str, // the length is stored as `u8`
+//! }
+//!
+//! let label: ThinBox<Label> = Label::init_unsized("label");
+//! assert_eq!(&label.text, "label");
+//! let too_long = Label::init_unsized_checked::<ThinBox<_>>(&"x".repeat(256));
+//! assert!(matches!(too_long, Err(InitError::TooLong { len: 256, max_len: 255, .. })));
+//! ```
+//!
+//! ### `cyclic`
+//! For `[T]` and `dyn Trait` last fields, generates `init_unsized_cyclic`, which constructs `Rc<Self>` or `Arc<Self>` (see [`Cyclic`])
+//! from a closure receiving a `Weak` reference to the allocation being built and returning the other fields.
+//! Like `Rc::new_cyclic`, the `Weak` can't be upgraded until the construction finishes.
+//! The last field is passed by value, so `[T]` is given as `[T; N]`.
+//! ```
+//! use dstify::Dstify;
+//! use std::rc::{Rc, Weak};
+//!
+//! #[derive(Dstify)]
+//! #[dstify(cyclic)]
+//! #[repr(C)]
+//! struct Node {
+//!     this: Weak<Node>,
+//!     children: [u32],
+//! }
+//!
+//! let node: Rc<Node> = Node::init_unsized_cyclic(
+//!     |weak: &Weak<Node>| {
+//!         assert!(weak.upgrade().is_none());
+//!         (weak.clone(),)
+//!     },
+//!     [1, 2, 3],
+//! );
+//! assert!(Rc::ptr_eq(&node.this.upgrade().unwrap(), &node));
+//! assert_eq!(node.children, [1, 2, 3]);
+//! ```
+//!
+//! ### `len_field = "name"`
+//! For `slice` DSTs, ties an integer field to the length of the last field, like the length of a C flexible array member.
+//! The constructors don't take that field and fill it in instead, failing like with `max_len` if the length doesn't fit it.
+//! `unsafe fn from_c_ptr(*const u8) -> &Self` reads the length field to view a struct allocated elsewhere, e.g. by C code.
+//! ```
+//! use dstify::Dstify;
+//!
+//! // struct msg { uint32_t len; uint8_t data[]; };
+//! #[derive(Dstify)]
+//! #[dstify(len_field = "len")]
+//! #[repr(C)]
+//! struct Msg {
+//!     len: u32,
+//!     data: [u8],
+//! }
+//!
+//! let msg: Box<Msg> = Msg::init_unsized(&[1, 2, 3]);
+//! assert_eq!(msg.len, 3);
+//!
+//! #[repr(C)]
+//! struct FromC {
+//!     len: u32,
+//!     data: [u8; 4],
+//! }
+//! let from_c = FromC { len: 2, data: [1, 2, 0, 0] };
+//! // SAFETY: `from_c` is laid out like `Msg` with 2 elements
+//! let msg = unsafe { Msg::from_c_ptr(std::ptr::from_ref(&from_c).cast()) };
+//! assert_eq!(msg.data, [1, 2]);
+//! ```
+//!
+//! ### `c_declaration`
+//! For `slice` DSTs, generates `const C_DECLARATION: &str` declaring the struct in C, with the last field as a flexible array member.
+//! The other fields must be primitives, `core::ffi` types, arrays of them or raw pointers to sized types, which are recognized by name,
+//! so type aliases aren't supported. Tuple struct fields are named `_0`, `_1`, ... Declarations can be combined into a header file
+//! by [`ffi::write_header`], which requires the "std" feature.
+//! ```
+//! use dstify::Dstify;
+//! use std::ffi::{c_int, c_void};
+//!
+//! #[derive(Dstify)]
+//! #[dstify(c_declaration)]
+//! #[repr(C)]
+//! struct Msg {
+//!     kind: c_int,
+//!     tags: [u8; 4],
+//!     next: *const c_void,
+//!     data: [u16],
+//! }
+//!
+//! assert_eq!(
+//!     Msg::C_DECLARATION,
+//!     "struct Msg {\n    int kind;\n    uint8_t tags[4];\n    const void *next;\n    uint16_t data[];\n};\n",
+//! );
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! #[derive(Dstify)]
+//! #[dstify(c_declaration)]
+//! #[repr(C)]
+//! struct Msg {
+//!     name: *const str, // fails to compile, a fat pointer has no C equivalent
+//!     data: [u8],
+//! }
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! # use std::ffi::c_void;
+//! #[derive(Dstify)]
+//! #[dstify(c_declaration)]
+//! #[repr(C)]
+//! struct Msg {
+//!     nothing: c_void, // fails to compile, only pointers to `void` exist in C
+//!     data: [u8],
+//! }
+//! ```
+//!
+//! ### `pod`
+//! For `[T]` last field, generates `ref_from_bytes` and `mut_from_bytes`, viewing bytes as the struct without copying.
+//! All fields and the elements of the last field must be [`Pod`] and there must be no padding between the fields,
+//! both checked at compile time. The bytes must be aligned to the struct and exactly as long as an instance of it,
+//! with the length of the last field computed from the bytes after the other fields, and not longer than `max_len` if given,
+//! otherwise [`FromBytesError`] is returned.
+//!
+//! `from_bytes` copies the bytes, which don't have to be aligned, into any [`SmartPointer`], while `as_bytes` returns the bytes
+//! of an instance including its trailing padding. All constructors zero the padding, so equal values always have equal bytes.
+//! As the trailing padding may hold elements of the last field, [`PodDst`], also implemented, converts bytes given the length
+//! of the last field, which the [`log`] module stores along with the records.
+//! ```
+//! use dstify::{Dstify, FromBytesError};
+//!
+//! #[derive(Dstify)]
+//! #[dstify(pod)]
+//! #[repr(C)]
+//! struct Packet {
+//!     kind: u32,
+//!     flags: u32,
+//!     body: [u8],
+//! }
+//!
+//! let mut words = [0u32; 3];
+//! let bytes: &mut [u8; 12] = unsafe { &mut *words.as_mut_ptr().cast() };
+//! bytes[..8].copy_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+//! let packet = Packet::mut_from_bytes(bytes).unwrap();
+//! packet.body.copy_from_slice(b"body");
+//! assert_eq!((u32::from_le(packet.kind), u32::from_le(packet.flags)), (1, 2));
+//! assert_eq!(&packet.body, b"body");
+//! assert_eq!(Packet::ref_from_bytes(&bytes[..10]).err(), Some(FromBytesError::Size { len: 10 }));
+//!
+//! let packet: Box<Packet> = Packet::init_unsized(1, 2, b"body");
+//! let copy: Box<Packet> = Packet::from_bytes(packet.as_bytes()).unwrap();
+//! assert_eq!(copy.as_bytes(), packet.as_bytes());
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! #[derive(Dstify)]
+//! #[dstify(pod)]
+//! #[repr(C)]
+//! struct Padded {
+//!     kind: u8, // followed by padding
+//!     body: [u32],
+//! }
+//! ```
+//!
+//! ### `endian = "le"` or `endian = "be"`
+//! Stores the integer fields before the last one in little-endian or big-endian byte order, like the field attributes
+//! `#[dstify(le)]` and `#[dstify(be)]`, which can be used on any field with `to_le`/`from_le` methods.
+//! The constructors convert the arguments, and getters and setters named like the fields (`field_0`, ... for tuple structs)
+//! convert from and to the native byte order, so `pod` views of the bytes mean the same on every architecture.
+//! The fields themselves, as well as those of a `sized_twin`, hold the converted values.
+//! ```
+//! use dstify::Dstify;
+//!
+//! #[derive(Dstify)]
+//! #[dstify(pod, endian = "le")]
+//! #[repr(C)]
+//! struct Entry {
+//!     key: u32,
+//!     #[dstify(be)]
+//!     checksum: u16,
+//!     flags: [u8; 2], // not an integer, stored as is
+//!     value: [u8],
+//! }
+//!
+//! let mut entry: Box<Entry> = Entry::init_unsized(1, 0xabcd, [0; 2], b"value");
+//! assert_eq!(entry.as_bytes()[..6], [1, 0, 0, 0, 0xab, 0xcd]);
+//! entry.set_key(entry.key() + 1);
+//! assert_eq!((entry.key(), entry.checksum()), (2, 0xabcd));
+//! ```
+//!
+//! ### `upcast_to(...)`
+//! Implements [`Upcast<Target>`](Upcast) and `From<Box<Self>> for Box<Target>` for every listed target, another struct deriving `Dstify`.
+//! The conversions reuse the allocation, turning the last field into a supertrait object or dropping auto traits.
+//! It's checked at compile time that the targets have the same types of the other fields in the same order,
+//! including their lifetimes, stored in the same byte order (see `endian`).
+//! ```
+//! use dstify::{Dstify, Upcast};
+//! use std::{fmt::Debug, rc::Rc};
+//!
+//! trait Task: Debug {}
+//! impl Task for u8 {}
+//!
+//! #[derive(Dstify)]
+//! #[dstify(upcast_to(AnyJob))]
+//! #[repr(C)]
+//! struct Job {
+//!     id: u64,
+//!     task: dyn Task + Send,
+//! }
+//!
+//! #[derive(Dstify)]
+//! #[repr(C)]
+//! struct AnyJob {
+//!     id: u64,
+//!     task: dyn Debug,
+//! }
+//!
+//! let job: Rc<Job> = Job::init_unsized(1, 2u8);
+//! let job: Rc<AnyJob> = Job::upcast_rc(job);
+//! assert_eq!(format!("{:?}", &job.task), "2");
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! # use std::fmt::Debug;
+//! #[derive(Dstify)]
+//! #[dstify(upcast_to(AnyJob))]
+//! #[repr(C)]
+//! struct Job {
+//!     id: u32,
+//!     task: dyn Debug,
+//! }
+//!
+//! #[derive(Dstify)]
+//! #[repr(C)]
+//! struct AnyJob {
+//!     id: u64, // fails to compile due to different type
+//!     task: dyn Debug,
+//! }
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! # use std::fmt::Debug;
+//! #[derive(Dstify)]
+//! #[dstify(upcast_to(Tagged<'static>))]
+//! #[repr(C)]
+//! struct Named<'a> {
+//!     name: &'a str, // fails to compile, the lifetime would be extended
+//!     value: dyn Debug,
+//! }
+//!
+//! #[derive(Dstify)]
+//! #[repr(C)]
+//! struct Tagged<'a> {
+//!     name: &'a str,
+//!     value: dyn Debug,
+//! }
+//! ```
+//! ```compile_fail
+//! # use dstify::Dstify;
+//! # use std::fmt::Debug;
+//! #[derive(Dstify)]
+//! #[dstify(endian = "le", upcast_to(Native))]
+//! #[repr(C)]
+//! struct Little {
+//!     id: u32, // fails to compile, the target reads it in native byte order
+//!     value: dyn Debug,
+//! }
+//!
+//! #[derive(Dstify)]
+//! #[repr(C)]
+//! struct Native {
+//!     id: u32,
+//!     value: dyn Debug,
+//! }
+//! ```
+//!
 //! ## Features
 //!
 //! - **"std"** - enabled by default  
 //!   removing this feature (using `default-features = false`) enables `!#[no_std]` support.
+//!   It's required by the [`ffi`] and [`log`] modules.
 
 extern crate alloc;
 
 #[doc(hidden)]
 pub mod private;
 
+mod error;
+#[cfg(feature = "std")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod log;
+mod pod;
 mod smart_pointer;
+mod upcast;
 
 pub use dstify_derive::Dstify;
-pub use smart_pointer::SmartPointer;
+pub use error::{FromBytesError, InitError};
+pub use pod::{Pod, PodDst};
+pub use smart_pointer::{
+    Cyclic, InlineDst, MakeMut, SmartPointer, ThinBox, ThinDst, ThinRc, UniqueRc, WeakThinRc,
+    dealloc,
+};
+#[cfg(target_has_atomic = "ptr")]
+pub use smart_pointer::{ThinArc, UniqueArc, WeakThinArc};
+pub use upcast::Upcast;
@@ -0,0 +1,340 @@
+//! Append-only logs of variable-length records, see [`RecordWriter`] and [`RecordReader`].
+//!
+//! Each record is stored as its length in bytes, the length of its last field and a CRC-32 checksum,
+//! all `u32` little-endian, followed by the bytes of the record. Records are padded with zeros to the alignment of the record type, at least 8, so that records
+//! in a log loaded into a suitably aligned buffer can be viewed in place.
+//!
+//! ```
+//! use dstify::{
+//!     Dstify,
+//!     log::{RecordReader, RecordWriter},
+//! };
+//!
+//! #[derive(Dstify)]
+//! #[dstify(pod, endian = "le")]
+//! #[repr(C)]
+//! struct Event {
+//!     id: u32,
+//!     payload: [u8],
+//! }
+//!
+//! let mut log = Vec::new();
+//! let mut writer = RecordWriter::<Event, _>::new(&mut log);
+//! writer.write(&Event::init_unsized::<Box<_>>(1, b"started"))?;
+//! writer.write(&Event::init_unsized::<Box<_>>(2, b"stopped"))?;
+//!
+//! let events = RecordReader::<Event, _>::new(&log[..]).collect::<Result<Vec<Box<Event>>, _>>()?;
+//! assert_eq!((events[1].id(), &events[1].payload), (2, &b"stopped"[..]));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::{FromBytesError, PodDst};
+use std::{
+    error, fmt,
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+
+/// size of the lengths and the checksum in front of each record
+const HEADER: usize = 12;
+
+/// Appends records of type `T` to `W`.
+pub struct RecordWriter<T: ?Sized, W> {
+    out: W,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized + PodDst, W: Write> RecordWriter<T, W> {
+    /// Writes records to `out`, which must be empty or end with a record written by a `RecordWriter<T, _>`.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Appends `record`, failing with [`io::ErrorKind::InvalidInput`] if it's longer than `u32::MAX` bytes
+    /// or its last field has more than `u32::MAX` elements.
+    pub fn write(&mut self, record: &T) -> io::Result<()> {
+        let bytes = record.as_bytes();
+        let too_long = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record exceeds `u32::MAX` bytes or elements",
+            )
+        };
+        let len = u32::try_from(bytes.len()).map_err(|_| too_long())?;
+        let tail_len = u32::try_from(record.tail_len()).map_err(|_| too_long())?;
+        let size = record_size::<T>(bytes.len()).ok_or_else(too_long)?;
+        let mut header = [0; HEADER];
+        header[..4].copy_from_slice(&len.to_le_bytes());
+        header[4..8].copy_from_slice(&tail_len.to_le_bytes());
+        let checksum = checksum(&header[..8], bytes);
+        header[8..].copy_from_slice(&checksum.to_le_bytes());
+        self.out.write_all(&header)?;
+        write_zeros(&mut self.out, payload_offset::<T>() - HEADER)?;
+        self.out.write_all(bytes)?;
+        write_zeros(&mut self.out, size - payload_offset::<T>() - bytes.len())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Reads records of type `T` written by [`RecordWriter`] from `R`.
+///
+/// As an [`Iterator`], copies each record into a `Box<T>`. A reader of `&[u8]` can instead view the records in place
+/// using [`views`](RecordReader::views). The iteration stops after the first error.
+pub struct RecordReader<T: ?Sized, R> {
+    input: R,
+    offset: u64,
+    failed: bool,
+    _marker: PhantomData<fn() -> Box<T>>,
+}
+
+impl<T: ?Sized + PodDst, R> RecordReader<T, R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            offset: 0,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.input
+    }
+}
+
+impl<'a, T: ?Sized + PodDst + 'a> RecordReader<T, &'a [u8]> {
+    /// Iterates references to the records in the remaining bytes, without copying.
+    ///
+    /// The bytes must be aligned to 8 or the alignment of `T` if larger, e.g. loaded into a `Vec<u64>`,
+    /// otherwise [`FromBytesError::Misaligned`] may be returned.
+    pub fn views(self) -> impl Iterator<Item = Result<&'a T, Error>> {
+        let Self {
+            input: mut bytes,
+            mut offset,
+            mut failed,
+            ..
+        } = self;
+        core::iter::from_fn(move || {
+            if failed || bytes.is_empty() {
+                return None;
+            }
+            let record = split_record::<T>(&mut bytes, offset).and_then(|(record, size)| {
+                let record_offset = offset;
+                offset += size as u64;
+                T::ref_from_bytes_with_len(record.bytes, record.tail_len).map_err(|error| {
+                    Error::Record {
+                        offset: record_offset,
+                        error,
+                    }
+                })
+            });
+            failed = record.is_err();
+            Some(record)
+        })
+    }
+}
+
+impl<T: ?Sized + PodDst, R: Read> Iterator for RecordReader<T, R> {
+    type Item = Result<Box<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let record = self.read().transpose()?;
+        self.failed = record.is_err();
+        Some(record)
+    }
+}
+
+impl<T: ?Sized + PodDst, R: Read> RecordReader<T, R> {
+    fn read(&mut self) -> Result<Option<Box<T>>, Error> {
+        let offset = self.offset;
+        let truncated = |err: io::Error| match err.kind() {
+            io::ErrorKind::UnexpectedEof => Error::Truncated { offset },
+            _ => Error::Io(err),
+        };
+        let mut header = [0; HEADER];
+        match self.input.read(&mut header[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => self.input.read_exact(&mut header[1..]).map_err(truncated)?,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => return self.read(),
+            Err(err) => return Err(Error::Io(err)),
+        }
+        let size = record_size::<T>(record_len(&header)).ok_or(Error::Truncated { offset })?;
+        // the length isn't checked yet, so the memory only grows with the bytes actually read
+        let mut payload = Vec::new();
+        (&mut self.input)
+            .take((size - HEADER) as u64)
+            .read_to_end(&mut payload)?;
+        if payload.len() != size - HEADER {
+            return Err(Error::Truncated { offset });
+        }
+        let record = check_record(&header, &payload[payload_offset::<T>() - HEADER..], offset)?;
+        self.offset += size as u64;
+        T::from_bytes_with_len(record.bytes, record.tail_len)
+            .map(Some)
+            .map_err(|error| Error::Record { offset, error })
+    }
+}
+
+/// Record checked against its checksum.
+struct Record<'a> {
+    bytes: &'a [u8],
+    tail_len: usize,
+}
+
+/// Splits the record at the start of `bytes` off, returning it and the size it occupied in the log.
+fn split_record<'a, T: ?Sized + PodDst>(
+    bytes: &mut &'a [u8],
+    offset: u64,
+) -> Result<(Record<'a>, usize), Error> {
+    let header = bytes
+        .first_chunk::<HEADER>()
+        .ok_or(Error::Truncated { offset })?;
+    let size = record_size::<T>(record_len(header)).ok_or(Error::Truncated { offset })?;
+    let (record, rest) = bytes
+        .split_at_checked(size)
+        .ok_or(Error::Truncated { offset })?;
+    let record = check_record(header, &record[payload_offset::<T>()..], offset)?;
+    *bytes = rest;
+    Ok((record, size))
+}
+
+fn word(header: &[u8; HEADER], i: usize) -> u32 {
+    u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]])
+}
+
+fn record_len(header: &[u8; HEADER]) -> usize {
+    word(header, 0) as usize
+}
+
+/// Checks the record with `header` whose bytes start `payload`.
+fn check_record<'a>(
+    header: &[u8; HEADER],
+    payload: &'a [u8],
+    offset: u64,
+) -> Result<Record<'a>, Error> {
+    let bytes = &payload[..record_len(header)];
+    if checksum(&header[..8], bytes) != word(header, 8) {
+        return Err(Error::Checksum { offset });
+    }
+    Ok(Record {
+        bytes,
+        tail_len: word(header, 4) as usize,
+    })
+}
+
+/// Alignment of the records in the log.
+fn align<T: ?Sized + PodDst>() -> usize {
+    T::ALIGN.max(8)
+}
+
+/// Offset of the bytes of a record from the start of its header.
+fn payload_offset<T: ?Sized + PodDst>() -> usize {
+    HEADER.next_multiple_of(align::<T>())
+}
+
+/// Size of a record of `len` bytes in the log, including its header and padding, unless it overflows.
+fn record_size<T: ?Sized + PodDst>(len: usize) -> Option<usize> {
+    len.checked_next_multiple_of(align::<T>())?
+        .checked_add(payload_offset::<T>())
+}
+
+fn write_zeros<W: Write>(out: &mut W, len: usize) -> io::Result<()> {
+    const ZEROS: [u8; 64] = [0; 64];
+    let mut len = len;
+    while len > 0 {
+        let chunk = len.min(ZEROS.len());
+        out.write_all(&ZEROS[..chunk])?;
+        len -= chunk;
+    }
+    Ok(())
+}
+
+/// CRC-32 (IEEE) of the lengths followed by the bytes of a record.
+fn checksum(lengths: &[u8], bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    0xedb8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    let crc = lengths.iter().chain(bytes).fold(!0u32, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    });
+    !crc
+}
+
+/// Error reading a log by [`RecordReader`].
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// the log ends in the middle of the record at `offset`
+    Truncated {
+        offset: u64,
+    },
+    /// the checksum of the record at `offset` doesn't match its bytes
+    Checksum {
+        offset: u64,
+    },
+    /// the bytes of the record at `offset` aren't a valid `T`
+    Record {
+        offset: u64,
+        error: FromBytesError,
+    },
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => fmt::Display::fmt(err, f),
+            Error::Truncated { offset } => write!(f, "log is truncated in the record at {offset}"),
+            Error::Checksum { offset } => write!(f, "checksum mismatch in the record at {offset}"),
+            Error::Record { offset, error } => write!(f, "invalid record at {offset}: {error}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Record { error, .. } => Some(error),
+            Error::Truncated { .. } | Error::Checksum { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{FromBytesError, SmartPointer};
+
+/// Plain old data: `Copy` types without padding, for which any bit pattern is a valid value.
+///
+/// Required by `#[dstify(pod)]` for all fields and the elements of the last field.
+/// Implemented for integers, floats and arrays of `Pod` types.
+///
+/// # Safety
+/// Every initialized bit pattern of the size of `Self` must be a valid `Self`, and `Self` must not contain padding.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! pod {
+    ($($ty:ty)*) => {$(
+        unsafe impl Pod for $ty {}
+    )*};
+}
+
+pod!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// DST whose fields are all [`Pod`], which can be converted from and to bytes.
+///
+/// Implemented by `#[dstify(pod)]`, forwarding to the generated methods of the same names.
+///
+/// The `*_with_len` methods take the length of the last field, as `ref_from_bytes` and `from_bytes` take as many
+/// elements as fit in the bytes, including the trailing padding, which may differ from the length of the value
+/// the bytes were taken from.
+///
+/// # Safety
+/// `as_bytes` must return all bytes of the value, which the `*_with_len` methods must accept given its `tail_len`,
+/// and `ALIGN` must be the alignment of `Self`.
+pub unsafe trait PodDst {
+    /// alignment of `Self`, regardless of the length of the last field
+    const ALIGN: usize;
+
+    fn as_bytes(&self) -> &[u8];
+
+    /// length of the last field
+    fn tail_len(&self) -> usize;
+
+    fn ref_from_bytes(bytes: &[u8]) -> Result<&Self, FromBytesError>;
+
+    fn from_bytes<R: SmartPointer<Self>>(bytes: &[u8]) -> Result<R, FromBytesError>;
+
+    /// Like `ref_from_bytes`, with `len` elements in the last field.
+    fn ref_from_bytes_with_len(bytes: &[u8], len: usize) -> Result<&Self, FromBytesError>;
+
+    /// Like `from_bytes`, with `len` elements in the last field.
+    fn from_bytes_with_len<R: SmartPointer<Self>>(
+        bytes: &[u8],
+        len: usize,
+    ) -> Result<R, FromBytesError>;
+}
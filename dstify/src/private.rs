@@ -1,69 +1,312 @@
-use crate::SmartPointer;
+use crate::{FromBytesError, InitError, SmartPointer};
 use core::{
     alloc::{Layout, LayoutError},
     ffi::CStr,
-    mem, ptr,
+    ptr,
 };
 
 #[cfg(feature = "std")]
 use std::{ffi::OsStr, path::Path};
 
-pub unsafe fn alloc_slice<T, R, D, F, const N: usize>(
+mod buf;
+mod resize;
+
+pub use alloc::{boxed::Box, rc::Rc, sync::Arc};
+pub use buf::{Buf, BufStorage, Storage};
+pub use resize::Resize;
+
+/// Types of the fields of a derived struct, used to check that two DSTs share their normal fields.
+pub trait DstFields {
+    /// tuple of the normal field types
+    type Normal;
+    /// tuple of the byte orders of the normal fields, [`NativeEndian`], [`LittleEndian`] or [`BigEndian`]
+    type Endian;
+    type Unsized: ?Sized;
+}
+
+/// Byte orders of the fields in [`DstFields::Endian`], see `#[dstify(endian = ...)]`.
+pub struct NativeEndian;
+pub struct LittleEndian;
+pub struct BigEndian;
+
+pub unsafe fn alloc_slice<T, R, D, F, C, const N: usize>(
     normal_fields: [core::alloc::Layout; N],
     unsized_field: &D,
     init_normal_fields: F,
-) -> Result<*const [u8], core::alloc::LayoutError>
+    cast: C,
+) -> Result<R, core::alloc::LayoutError>
 where
     T: ?Sized,
     R: SmartPointer<T>,
     D: AsSlice + ?Sized,
     F: FnOnce(&mut Offsets<N>),
+    C: FnOnce(*const [u8]) -> *mut T,
 {
     let slice = unsized_field.as_slice();
     let (layout, offsets, last_offset) =
         calc_offsets(normal_fields, Layout::array::<D::Item>(slice.len())?)?;
-    let (base, guard) = unsafe { R::alloc(layout) };
-
-    let mut offsets = Offsets {
-        base,
-        offsets,
-        curr: 0,
-    };
-    init_normal_fields(&mut offsets);
-    if !slice.is_empty() {
+    let init = |base: *mut u8| {
         unsafe {
-            let dest = base.add(last_offset).cast();
-            ptr::copy_nonoverlapping(slice.as_ptr(), dest, slice.len())
+            zero_padding(base, &normal_fields, &offsets, last_offset);
+            zero_trailing(base, last_offset + size_of_val(slice), layout.size());
         }
-    }
-
-    mem::forget(guard);
-    Ok(::core::ptr::slice_from_raw_parts_mut(base, slice.len()))
+        let mut offsets = Offsets {
+            base,
+            offsets,
+            curr: 0,
+        };
+        init_normal_fields(&mut offsets);
+        if !slice.is_empty() {
+            unsafe {
+                let dest = base.add(last_offset).cast();
+                ptr::copy_nonoverlapping(slice.as_ptr(), dest, slice.len())
+            }
+        }
+        cast(::core::ptr::slice_from_raw_parts_mut(base, slice.len()))
+    };
+    Ok(unsafe { R::new(layout, init) })
 }
 
-pub unsafe fn alloc_dyn<T, R, D, F, const N: usize>(
+pub unsafe fn alloc_dyn<T, R, D, F, C, const N: usize>(
     normal_fields: [core::alloc::Layout; N],
     unsized_field: D,
     init_normal_fields: F,
-) -> Result<*const u8, core::alloc::LayoutError>
+    cast: C,
+) -> Result<R, core::alloc::LayoutError>
 where
     T: ?Sized,
     R: SmartPointer<T>,
     F: FnOnce(&mut Offsets<N>),
+    C: FnOnce(*const u8) -> *mut T,
 {
     let (layout, offsets, last_offset) = calc_offsets(normal_fields, Layout::new::<D>())?;
-    let (base, guard) = unsafe { R::alloc(layout) };
+    let init = |base: *mut u8| {
+        unsafe {
+            zero_padding(base, &normal_fields, &offsets, last_offset);
+            zero_trailing(base, last_offset + size_of::<D>(), layout.size());
+        }
+        let mut offsets = Offsets {
+            base,
+            offsets,
+            curr: 0,
+        };
+        init_normal_fields(&mut offsets);
+        unsafe { ptr::write(base.add(last_offset).cast(), unsized_field) };
+        cast(base)
+    };
+    Ok(unsafe { R::new(layout, init) })
+}
+
+/// Fails if the last field has more than `max_len` elements, see `#[dstify(max_len = ...)]`.
+#[inline]
+pub fn check_len<D: AsSlice + ?Sized>(unsized_field: &D, max_len: usize) -> Result<(), InitError> {
+    check_new_len(unsized_field.as_slice().len(), max_len)
+}
+
+/// Fails if the last field would be resized to more than `max_len` elements.
+#[inline]
+pub fn check_new_len(len: usize, max_len: usize) -> Result<(), InitError> {
+    if len > max_len {
+        return Err(InitError::TooLong {
+            len,
+            max_len,
+            len_field: None,
+        });
+    }
+    Ok(())
+}
+
+/// Integer types that can hold the length of the last field, see `#[dstify(len_field = ...)]`.
+pub trait LenField: Copy {
+    /// the largest length representable, saturated to `usize`
+    const MAX: usize;
+    fn from_len(len: usize) -> Option<Self>;
+    fn into_len(self) -> usize;
+}
 
-    let mut offsets = Offsets {
-        base,
-        offsets,
-        curr: 0,
+macro_rules! len_field {
+    ($($ty:ty)*) => {$(
+        impl LenField for $ty {
+            const MAX: usize = if <$ty>::MAX as u128 > usize::MAX as u128 {
+                usize::MAX
+            } else {
+                <$ty>::MAX as usize
+            };
+            #[inline]
+            fn from_len(len: usize) -> Option<Self> {
+                Self::try_from(len).ok()
+            }
+            #[inline]
+            fn into_len(self) -> usize {
+                usize::try_from(self).expect("length field is out of range")
+            }
+        }
+    )*};
+}
+
+len_field!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize);
+
+/// Converts the length of the last field into the type of its `#[dstify(len_field = ...)]` named `name`.
+#[inline]
+pub fn len_field<L: LenField, D: AsSlice + ?Sized>(
+    unsized_field: &D,
+    name: &'static str,
+) -> Result<L, InitError> {
+    let len = unsized_field.as_slice().len();
+    L::from_len(len).ok_or(InitError::TooLong {
+        len,
+        max_len: L::MAX,
+        len_field: Some(name),
+    })
+}
+
+/// Fails to compile if `T` is unsized.
+pub const fn assert_sized<T>() {}
+
+/// Fails to compile if the fields of a `#[dstify(pod)]` struct are separated by padding.
+pub const fn assert_pod_layout<const N: usize>(normal_fields: [Layout; N], elem: Layout) {
+    let mut offset = 0;
+    let mut i = 0;
+    while i < N {
+        assert!(
+            offset % normal_fields[i].align() == 0,
+            "`#[dstify(pod)]` fields must not be separated by padding"
+        );
+        offset += normal_fields[i].size();
+        i += 1;
+    }
+    assert!(
+        offset % elem.align() == 0,
+        "`#[dstify(pod)]` fields must not be separated by padding"
+    );
+}
+
+/// The largest of `aligns`, the alignment of a struct whose fields have `aligns`.
+pub const fn max_align<const N: usize>(aligns: [usize; N]) -> usize {
+    let mut max = 1;
+    let mut i = 0;
+    while i < N {
+        if aligns[i] > max {
+            max = aligns[i];
+        }
+        i += 1;
+    }
+    max
+}
+
+/// Length of the last field of a `#[dstify(pod)]` struct occupying exactly `bytes`, which must be aligned for it.
+///
+/// Unless given, the length is the most elements fitting in `bytes`, as their trailing padding may hold some.
+/// It may not exceed `max_len`, see `#[dstify(max_len = ...)]`.
+pub fn pod_len<const N: usize>(
+    normal_fields: [Layout; N],
+    elem: Layout,
+    bytes: &[u8],
+    len: Option<usize>,
+    max_len: usize,
+) -> Result<usize, FromBytesError> {
+    let (len, layout, _) = pod_layout(normal_fields, elem, bytes.len(), len, max_len)?;
+    if !bytes.as_ptr().addr().is_multiple_of(layout.align()) {
+        return Err(FromBytesError::Misaligned {
+            align: layout.align(),
+        });
+    }
+    Ok(len)
+}
+
+/// Copies `bytes` into a new allocation holding a `#[dstify(pod)]` struct, zeroing its trailing padding.
+pub unsafe fn pod_from_bytes<T, R, C, const N: usize>(
+    normal_fields: [Layout; N],
+    elem: Layout,
+    bytes: &[u8],
+    len: Option<usize>,
+    max_len: usize,
+    cast: C,
+) -> Result<R, FromBytesError>
+where
+    T: ?Sized,
+    R: SmartPointer<T>,
+    C: FnOnce(*const [u8]) -> *mut T,
+{
+    let (len, layout, last_offset) = pod_layout(normal_fields, elem, bytes.len(), len, max_len)?;
+    let init = |base: *mut u8| {
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), base, bytes.len());
+            zero_trailing(base, last_offset + elem.size() * len, layout.size());
+        }
+        cast(ptr::slice_from_raw_parts_mut(base, len))
     };
-    init_normal_fields(&mut offsets);
-    unsafe { ptr::write(base.add(last_offset).cast(), unsized_field) };
+    Ok(unsafe { R::new(layout, init) })
+}
 
-    mem::forget(guard);
-    Ok(base)
+/// Length of the last field, layout and offset of the last field of a `#[dstify(pod)]` struct of `size` bytes,
+/// see [`pod_len`].
+fn pod_layout<const N: usize>(
+    normal_fields: [Layout; N],
+    elem: Layout,
+    size: usize,
+    len: Option<usize>,
+    max_len: usize,
+) -> Result<(usize, Layout, usize), FromBytesError> {
+    let size_error = FromBytesError::Size { len: size };
+    let (_, _, last_offset) = slice_layout_of(normal_fields, elem, 0).ok_or(size_error)?;
+    let rest = size.checked_sub(last_offset).ok_or(size_error)?;
+    let len = match (len, elem.size()) {
+        (Some(len), _) => len,
+        (None, 0) => 0,
+        (None, elem_size) => rest / elem_size,
+    };
+    if len > max_len {
+        return Err(FromBytesError::TooLong { len, max_len });
+    }
+    match slice_layout_of(normal_fields, elem, len) {
+        Some((layout, _, _)) if layout.size() == size => Ok((len, layout, last_offset)),
+        _ => Err(size_error),
+    }
+}
+
+/// Layout of a DST with `len` elements of layout `elem` in the last field, see [`calc_offsets`].
+fn slice_layout_of<const N: usize>(
+    normal_fields: [Layout; N],
+    elem: Layout,
+    len: usize,
+) -> Option<(Layout, [usize; N], usize)> {
+    let array = Layout::from_size_align(elem.size().checked_mul(len)?, elem.align()).ok()?;
+    calc_offsets(normal_fields, array).ok()
+}
+
+/// Zeroes the padding `calc_offsets` puts between the normal fields and in front of the last field,
+/// so that identical values have identical bytes.
+///
+/// # Safety
+/// `base` must be valid for writes of `last_offset` bytes.
+unsafe fn zero_padding<const N: usize>(
+    base: *mut u8,
+    normal_fields: &[Layout; N],
+    offsets: &[usize; N],
+    last_offset: usize,
+) {
+    let mut end = 0;
+    for (layout, &offset) in normal_fields.iter().zip(offsets) {
+        unsafe { ptr::write_bytes(base.add(end), 0, offset - end) };
+        end = offset + layout.size();
+    }
+    unsafe { ptr::write_bytes(base.add(end), 0, last_offset - end) };
+}
+
+/// Zeroes the trailing padding between the end of the last field at `end` and the size of the value.
+///
+/// # Safety
+/// `base` must be valid for writes of `size` bytes.
+pub unsafe fn zero_trailing(base: *mut u8, end: usize, size: usize) {
+    unsafe { ptr::write_bytes(base.add(end), 0, size - end) };
+}
+
+/// Frees memory allocated by [`SmartPointer::alloc`] for `Box` without dropping its contents.
+pub unsafe fn dealloc(base: *mut u8, layout: Layout) {
+    if layout.size() != 0 {
+        unsafe { alloc::alloc::dealloc(base, layout) };
+    }
 }
 
 #[inline]
@@ -86,6 +329,33 @@ fn calc_offsets<const N: usize>(
     Ok((layout.pad_to_align(), offsets, last_offset))
 }
 
+/// Layout of a DST with `len` elements of type `E` in the last field, see [`calc_offsets`].
+fn slice_layout<E, const N: usize>(
+    normal_fields: [Layout; N],
+    len: usize,
+) -> (Layout, [usize; N], usize) {
+    Layout::array::<E>(len)
+        .and_then(|array| calc_offsets(normal_fields, array))
+        .expect("layout exceeds `isize::MAX` bytes")
+}
+
+/// `realloc` that also handles zero-sized layouts the way [`SmartPointer::alloc`](crate::SmartPointer::alloc) does for `Box`
+unsafe fn realloc_layout(base: *mut u8, old: Layout, new: Layout) -> *mut u8 {
+    let base = match (old.size(), new.size()) {
+        (0, 0) => return base,
+        (0, _) => unsafe { alloc::alloc::alloc(new) },
+        (_, 0) => {
+            unsafe { alloc::alloc::dealloc(base, old) };
+            return ptr::without_provenance_mut(new.align());
+        }
+        (_, new_size) => unsafe { alloc::alloc::realloc(base, old, new_size) },
+    };
+    if base.is_null() {
+        alloc::alloc::handle_alloc_error(new);
+    }
+    base
+}
+
 pub struct Offsets<const N: usize> {
     pub(super) base: *mut u8,
     pub(super) offsets: [usize; N],
@@ -0,0 +1,271 @@
+use super::{Offsets, realloc_layout, slice_layout, zero_padding, zero_trailing};
+use crate::{SmartPointer, smart_pointer::uninit};
+use alloc::{alloc::Layout, boxed::Box, rc::Rc};
+use core::{
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ptr,
+};
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// Kind of allocation a [`Buf`] lives in. Freezing into the matching pointer reuses it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Storage {
+    Box,
+    Rc,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc,
+}
+
+impl Storage {
+    fn alloc(self, layout: Layout) -> *mut u8 {
+        match self {
+            Storage::Box => {
+                let (base, guard) = unsafe { Box::<u8>::alloc(layout) };
+                mem::forget(guard);
+                base
+            }
+            Storage::Rc => {
+                let (base, guard) = uninit::alloc::<Rc<u8>>(layout);
+                mem::forget(guard);
+                base
+            }
+            #[cfg(target_has_atomic = "ptr")]
+            Storage::Arc => {
+                let (base, guard) = uninit::alloc::<Arc<u8>>(layout);
+                mem::forget(guard);
+                base
+            }
+        }
+    }
+
+    /// # Safety
+    /// `base` must come from `self.alloc(layout)`. Nothing is dropped.
+    unsafe fn free(self, base: *mut u8, layout: Layout) {
+        match self {
+            Storage::Box => unsafe { super::dealloc(base, layout) },
+            Storage::Rc => unsafe { uninit::free::<Rc<u8>>(base, layout) },
+            #[cfg(target_has_atomic = "ptr")]
+            Storage::Arc => unsafe { uninit::free::<Arc<u8>>(base, layout) },
+        }
+    }
+}
+
+/// Pointer a [`Buf`] can be frozen into without copying, when allocated in its [`Storage`].
+pub trait BufStorage<T: ?Sized> {
+    const STORAGE: Storage;
+}
+
+impl<T: ?Sized> BufStorage<T> for Box<T> {
+    const STORAGE: Storage = Storage::Box;
+}
+
+impl<T: ?Sized> BufStorage<T> for Rc<T> {
+    const STORAGE: Storage = Storage::Rc;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: ?Sized> BufStorage<T> for Arc<T> {
+    const STORAGE: Storage = Storage::Arc;
+}
+
+/// Frees the memory of a [`Buf`] if the initialization of its normal fields panics.
+struct FreeGuard {
+    storage: Storage,
+    base: *mut u8,
+    layout: Layout,
+}
+
+impl Drop for FreeGuard {
+    fn drop(&mut self) {
+        unsafe { self.storage.free(self.base, self.layout) };
+    }
+}
+
+/// Allocation of a DST with `[E]` last field, with room for `cap` elements, of which `len` are initialized.
+///
+/// The normal fields are always initialized, so `cast(base, len)` is always a valid `T`.
+pub struct Buf<T: ?Sized, E, const N: usize> {
+    storage: Storage,
+    base: *mut u8,
+    len: usize,
+    cap: usize,
+    normal_fields: [Layout; N],
+    last_offset: usize,
+    cast: fn(*mut u8, usize) -> *mut T,
+    _marker: PhantomData<(Box<T>, E)>,
+}
+
+unsafe impl<T: ?Sized + Send, E: Send, const N: usize> Send for Buf<T, E, N> {}
+unsafe impl<T: ?Sized + Sync, E: Sync, const N: usize> Sync for Buf<T, E, N> {}
+
+impl<T: ?Sized, E, const N: usize> Buf<T, E, N> {
+    /// # Safety
+    /// `normal_fields` must describe the normal fields of `T`, `init_normal_fields` must initialize all of them,
+    /// `E` must be the type of the elements of the last field and `cast` must rebuild the fat pointer from its base and length.
+    pub unsafe fn new<F>(
+        storage: Storage,
+        normal_fields: [Layout; N],
+        cap: usize,
+        cast: fn(*mut u8, usize) -> *mut T,
+        init_normal_fields: F,
+    ) -> Self
+    where
+        F: FnOnce(&mut Offsets<N>),
+    {
+        let (layout, offsets, last_offset) = slice_layout::<E, N>(normal_fields, cap);
+        let base = storage.alloc(layout);
+        let guard = FreeGuard {
+            storage,
+            base,
+            layout,
+        };
+        // the spare capacity is zeroed as well, so that the padding past the elements always is
+        unsafe {
+            zero_padding(base, &normal_fields, &offsets, last_offset);
+            zero_trailing(base, last_offset, layout.size());
+        }
+        init_normal_fields(&mut Offsets {
+            base,
+            offsets,
+            curr: 0,
+        });
+        mem::forget(guard);
+        Self {
+            storage,
+            base,
+            len: 0,
+            cap,
+            normal_fields,
+            last_offset,
+            cast,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *mut T {
+        (self.cast)(self.base, self.len)
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        if self.cap - self.len >= additional {
+            return;
+        }
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        let new_cap = required.max(self.cap.saturating_mul(2)).max(4);
+        unsafe { self.realloc(new_cap) };
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if self.cap != self.len {
+            unsafe { self.realloc(self.len) };
+        }
+    }
+
+    pub fn push(&mut self, value: E) {
+        if self.len == self.cap {
+            self.reserve(1);
+        }
+        unsafe { ptr::write(self.elem(self.len), value) };
+        self.len += 1;
+    }
+
+    pub fn extend_from_slice(&mut self, values: &[E])
+    where
+        E: Clone,
+    {
+        self.reserve(values.len());
+        for value in values {
+            self.push(value.clone());
+        }
+    }
+
+    /// Moves the value into an allocation of `storage` fitting it exactly, unless it already is in one,
+    /// and gives up its ownership.
+    fn freeze(mut self, storage: Storage) -> *mut T {
+        if self.storage != storage || self.cap != self.len {
+            unsafe { self.move_to(storage, self.len) };
+        }
+        ManuallyDrop::new(self).as_ptr()
+    }
+
+    pub fn into_box(self) -> Box<T> {
+        unsafe { Box::from_raw(self.freeze(Storage::Box)) }
+    }
+
+    pub fn into_rc(self) -> Rc<T> {
+        unsafe { Rc::from_raw(self.freeze(Storage::Rc)) }
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn into_arc(self) -> Arc<T> {
+        unsafe { Arc::from_raw(self.freeze(Storage::Arc)) }
+    }
+
+    unsafe fn elem(&self, index: usize) -> *mut E {
+        unsafe { self.base.add(self.last_offset).cast::<E>().add(index) }
+    }
+
+    /// # Safety
+    /// Elements past `new_cap` must not be initialized.
+    unsafe fn realloc(&mut self, new_cap: usize) {
+        unsafe { self.move_to(self.storage, new_cap) };
+    }
+
+    /// Moves the allocation into `storage` with room for `new_cap` elements, reallocating in place if possible.
+    ///
+    /// # Safety
+    /// Elements past `new_cap` must not be initialized.
+    unsafe fn move_to(&mut self, storage: Storage, new_cap: usize) {
+        let (old_layout, _, _) = slice_layout::<E, N>(self.normal_fields, self.cap);
+        let (new_layout, _, _) = slice_layout::<E, N>(self.normal_fields, new_cap);
+        self.base = match (self.storage, storage) {
+            (Storage::Box, Storage::Box) => unsafe {
+                realloc_layout(self.base, old_layout, new_layout)
+            },
+            // `Rc` and `Arc` can't reallocate, their counts are in front of the value
+            _ => {
+                let base = storage.alloc(new_layout);
+                unsafe {
+                    let size = old_layout.size().min(new_layout.size());
+                    ptr::copy_nonoverlapping(self.base, base, size);
+                    self.storage.free(self.base, old_layout);
+                }
+                base
+            }
+        };
+        self.storage = storage;
+        if new_layout.size() > old_layout.size() {
+            unsafe { zero_trailing(self.base, old_layout.size(), new_layout.size()) };
+        }
+        self.cap = new_cap;
+    }
+}
+
+impl<T: ?Sized, E, const N: usize> Drop for Buf<T, E, N> {
+    fn drop(&mut self) {
+        let (layout, _, _) = slice_layout::<E, N>(self.normal_fields, self.cap);
+        unsafe {
+            ptr::drop_in_place(self.as_ptr());
+            self.storage.free(self.base, layout);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use super::{realloc_layout, slice_layout, zero_trailing};
+use alloc::{alloc::Layout, boxed::Box};
+use core::{mem, ptr};
+
+/// Resizes the `[E]` last field of a uniquely owned `Box<T>` in place.
+///
+/// Whatever happens, including panics while producing or dropping elements,
+/// the `Box` gets written back on drop with the memory reallocated to fit the initialized elements.
+pub struct Resize<'a, T: ?Sized, E, const N: usize> {
+    this: &'a mut Box<T>,
+    normal_fields: [Layout; N],
+    cast: fn(*mut u8, usize) -> *mut T,
+    base: *mut u8,
+    last_offset: usize,
+    /// number of elements the memory is allocated for
+    cap: usize,
+    /// number of initialized elements
+    len: usize,
+    _elem: core::marker::PhantomData<E>,
+}
+
+impl<'a, T: ?Sized, E, const N: usize> Resize<'a, T, E, N> {
+    /// # Safety
+    /// `this` must have been allocated by [`SmartPointer::alloc`](crate::SmartPointer::alloc) for `Box`,
+    /// `normal_fields` and `len` must describe it and `cast` must rebuild the fat pointer from its base and length.
+    pub unsafe fn new(
+        this: &'a mut Box<T>,
+        normal_fields: [Layout; N],
+        len: usize,
+        cast: fn(*mut u8, usize) -> *mut T,
+    ) -> Self {
+        let (_, _, last_offset) = slice_layout::<E, N>(normal_fields, len);
+        // the `Box` is logically moved out of `this` until `drop` writes it back
+        let base = Box::into_raw(unsafe { ptr::read(this) }).cast::<u8>();
+        Self {
+            this,
+            normal_fields,
+            cast,
+            base,
+            last_offset,
+            cap: len,
+            len,
+            _elem: core::marker::PhantomData,
+        }
+    }
+
+    /// Drops the elements past `len`.
+    pub fn truncate(mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let old_len = mem::replace(&mut self.len, len);
+        unsafe {
+            let first = self.elem(len);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(first, old_len - len));
+        }
+    }
+
+    /// Appends `additional` elements produced by `f`.
+    pub fn extend_with<F: FnMut() -> E>(mut self, additional: usize, mut f: F) {
+        let new_len = self.len.checked_add(additional).expect("capacity overflow");
+        unsafe { self.realloc(new_len) };
+        while self.len < new_len {
+            let elem = f();
+            unsafe { ptr::write(self.elem(self.len), elem) };
+            self.len += 1;
+        }
+    }
+
+    unsafe fn elem(&self, index: usize) -> *mut E {
+        unsafe { self.base.add(self.last_offset).cast::<E>().add(index) }
+    }
+
+    /// # Safety
+    /// Elements past `new_cap` must not be initialized.
+    unsafe fn realloc(&mut self, new_cap: usize) {
+        let (old_layout, _, _) = slice_layout::<E, N>(self.normal_fields, self.cap);
+        let (new_layout, _, _) = slice_layout::<E, N>(self.normal_fields, new_cap);
+        self.base = unsafe { realloc_layout(self.base, old_layout, new_layout) };
+        self.cap = new_cap;
+    }
+}
+
+impl<T: ?Sized, E, const N: usize> Drop for Resize<'_, T, E, N> {
+    fn drop(&mut self) {
+        if self.cap != self.len {
+            unsafe { self.realloc(self.len) };
+        }
+        // the trailing padding may hold bytes of removed elements
+        let (layout, _, _) = slice_layout::<E, N>(self.normal_fields, self.len);
+        let end = self.last_offset + self.len * size_of::<E>();
+        unsafe { zero_trailing(self.base, end, layout.size()) };
+        let this = unsafe { Box::from_raw((self.cast)(self.base, self.len)) };
+        unsafe { ptr::write(self.this, this) };
+    }
+}
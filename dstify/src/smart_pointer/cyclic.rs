@@ -0,0 +1,52 @@
+use super::Sealed;
+use alloc::{rc, sync};
+
+/// Smart pointers whose allocation can be referenced weakly while the value is constructed, see `#[dstify(cyclic)]`.
+///
+/// Implemented for [`Rc`](alloc::rc::Rc) and [`Arc`](alloc::sync::Arc) on top of their `new_cyclic`,
+/// so the `Weak` can't be upgraded until the construction finishes.
+pub trait Cyclic<T: ?Sized>: Sealed + Sized {
+    type Weak;
+
+    /// Constructs a sized `S` with the same layout as `T` using `init`, then converts it into `T` using `cast`.
+    ///
+    /// # Safety
+    /// `cast` must return a pointer to `T` with the same address, size and alignment as `S`.
+    #[doc(hidden)]
+    unsafe fn new_cyclic<S, F, C>(init: F, cast: C) -> Self
+    where
+        F: FnOnce(&Self::Weak) -> S,
+        C: Fn(*const S) -> *const T;
+}
+
+impl<T: ?Sized> Cyclic<T> for rc::Rc<T> {
+    type Weak = rc::Weak<T>;
+
+    unsafe fn new_cyclic<S, F, C>(init: F, cast: C) -> Self
+    where
+        F: FnOnce(&Self::Weak) -> S,
+        C: Fn(*const S) -> *const T,
+    {
+        let rc = rc::Rc::new_cyclic(|weak| {
+            let weak = unsafe { rc::Weak::from_raw(cast(rc::Weak::into_raw(weak.clone()))) };
+            init(&weak)
+        });
+        unsafe { rc::Rc::from_raw(cast(rc::Rc::into_raw(rc))) }
+    }
+}
+
+impl<T: ?Sized> Cyclic<T> for sync::Arc<T> {
+    type Weak = sync::Weak<T>;
+
+    unsafe fn new_cyclic<S, F, C>(init: F, cast: C) -> Self
+    where
+        F: FnOnce(&Self::Weak) -> S,
+        C: Fn(*const S) -> *const T,
+    {
+        let arc = sync::Arc::new_cyclic(|weak| {
+            let weak = unsafe { sync::Weak::from_raw(cast(sync::Weak::into_raw(weak.clone()))) };
+            init(&weak)
+        });
+        unsafe { sync::Arc::from_raw(cast(sync::Arc::into_raw(arc))) }
+    }
+}
@@ -0,0 +1,148 @@
+use super::{DropGuard, Sealed, SmartPointer, ThinDst};
+use alloc::{alloc::Layout, boxed::Box};
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::{MaybeUninit, align_of},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// Dynamically-sized `T`, stored inline if it fits in `N` bytes aligned like `usize`, or on the heap otherwise.
+///
+/// Only DSTs, whose pointer metadata stays valid when moved, are stored inline, see [`ThinDst::RELOCATABLE`].
+/// `dyn Trait` DSTs are always stored on the heap.
+///
+/// ```
+/// use dstify::{Dstify, InlineDst};
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Msg {
+///     kind: u8,
+///     text: str,
+/// }
+///
+/// let short: InlineDst<Msg, 48> = Msg::init_unsized(1, "short");
+/// assert!(InlineDst::is_inline(&short));
+/// let long: InlineDst<Msg, 48> = Msg::init_unsized(2, &"long".repeat(20));
+/// assert!(!InlineDst::is_inline(&long));
+/// assert_eq!((short.kind, &short.text), (1, "short"));
+/// ```
+pub struct InlineDst<T: ?Sized + ThinDst, const N: usize>(Repr<T, N>);
+
+enum Repr<T: ?Sized + ThinDst, const N: usize> {
+    Inline {
+        metadata: T::Metadata,
+        storage: Storage<N>,
+        _marker: PhantomData<T>,
+    },
+    Heap(Box<T>),
+}
+
+#[repr(C)]
+struct Storage<const N: usize> {
+    _align: [usize; 0],
+    bytes: [MaybeUninit<u8>; N],
+}
+
+unsafe impl<T: ?Sized + ThinDst + Send, const N: usize> Send for InlineDst<T, N> {}
+unsafe impl<T: ?Sized + ThinDst + Sync, const N: usize> Sync for InlineDst<T, N> {}
+
+impl<T: ?Sized + ThinDst, const N: usize> InlineDst<T, N> {
+    /// Returns `true` if the value is stored inline, not on the heap.
+    #[inline]
+    pub fn is_inline(this: &Self) -> bool {
+        matches!(this.0, Repr::Inline { .. })
+    }
+
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        match &this.0 {
+            Repr::Inline {
+                metadata, storage, ..
+            } => T::from_metadata(storage.bytes.as_ptr().cast_mut().cast(), *metadata),
+            Repr::Heap(boxed) => &**boxed,
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(this: &mut Self) -> *mut T {
+        match &mut this.0 {
+            Repr::Inline {
+                metadata, storage, ..
+            } => T::from_metadata(storage.bytes.as_mut_ptr().cast(), *metadata),
+            Repr::Heap(boxed) => &mut **boxed,
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst, const N: usize> Sealed for InlineDst<T, N> {}
+
+impl<T: ?Sized + ThinDst, const N: usize> SmartPointer<T> for InlineDst<T, N> {
+    type Guard = DropGuard;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        unsafe { Box::<T>::alloc(layout) }
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        Self(Repr::Heap(unsafe { Box::from_raw(base) }))
+    }
+
+    unsafe fn new<F>(layout: Layout, init: F) -> Self
+    where
+        F: FnOnce(*mut u8) -> *mut T,
+    {
+        if !T::RELOCATABLE || layout.size() > N || layout.align() > align_of::<Storage<N>>() {
+            return Self(Repr::Heap(unsafe {
+                <Box<T> as SmartPointer<T>>::new(layout, init)
+            }));
+        }
+        let mut storage = Storage {
+            _align: [],
+            bytes: [MaybeUninit::uninit(); N],
+        };
+        let ptr = init(storage.bytes.as_mut_ptr().cast());
+        Self(Repr::Inline {
+            metadata: T::metadata(ptr),
+            storage,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: ?Sized + ThinDst, const N: usize> Drop for InlineDst<T, N> {
+    fn drop(&mut self) {
+        if let Repr::Inline { .. } = self.0 {
+            unsafe { ptr::drop_in_place(Self::as_mut_ptr(self)) };
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst, const N: usize> Deref for InlineDst<T, N> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*Self::as_ptr(self) }
+    }
+}
+
+impl<T: ?Sized + ThinDst, const N: usize> DerefMut for InlineDst<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *Self::as_mut_ptr(self) }
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Debug, const N: usize> fmt::Debug for InlineDst<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Display, const N: usize> fmt::Display for InlineDst<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,43 @@
+use super::{SmartPointer, ThinDst, ThinRc};
+use alloc::{rc::Rc, sync::Arc};
+use core::ops::Deref;
+
+#[cfg(target_has_atomic = "ptr")]
+use super::ThinArc;
+
+/// Shared smart pointers, whose value can be cloned into a fresh allocation by `make_mut`, see `#[dstify(make_mut)]`.
+///
+/// Implemented for [`Rc`](alloc::rc::Rc), [`Arc`](alloc::sync::Arc), [`ThinRc`] and [`ThinArc`].
+pub trait MakeMut<T: ?Sized>: SmartPointer<T> + Deref<Target = T> + Sized {
+    /// Returns a mutable reference to the value, if there are no other pointers to the same allocation.
+    fn get_mut(this: &mut Self) -> Option<&mut T>;
+}
+
+impl<T: ?Sized> MakeMut<T> for Rc<T> {
+    #[inline]
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        Rc::get_mut(this)
+    }
+}
+
+impl<T: ?Sized> MakeMut<T> for Arc<T> {
+    #[inline]
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        Arc::get_mut(this)
+    }
+}
+
+impl<T: ?Sized + ThinDst> MakeMut<T> for ThinRc<T> {
+    #[inline]
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        ThinRc::get_mut(this)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: ?Sized + ThinDst> MakeMut<T> for ThinArc<T> {
+    #[inline]
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        ThinArc::get_mut(this)
+    }
+}
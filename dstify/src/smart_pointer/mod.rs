@@ -1,8 +1,35 @@
 mod arc;
 mod boxed;
+mod cyclic;
+mod inline_dst;
+mod make_mut;
+mod non_null;
+mod pin;
 mod rc;
+#[cfg(target_has_atomic = "ptr")]
+mod thin_arc;
+mod thin_box;
+mod thin_rc;
+mod thin_shared;
+pub(crate) mod uninit;
+#[cfg(target_has_atomic = "ptr")]
+mod unique_arc;
+mod unique_rc;
 
-use alloc::alloc::{Layout, dealloc};
+pub use cyclic::Cyclic;
+pub use inline_dst::InlineDst;
+pub use make_mut::MakeMut;
+pub use non_null::dealloc;
+#[cfg(target_has_atomic = "ptr")]
+pub use thin_arc::{ThinArc, WeakThinArc};
+pub use thin_box::ThinBox;
+pub use thin_rc::{ThinRc, WeakThinRc};
+#[cfg(target_has_atomic = "ptr")]
+pub use unique_arc::UniqueArc;
+pub use unique_rc::UniqueRc;
+
+use alloc::alloc::Layout;
+use core::mem;
 
 pub trait Sealed {}
 
@@ -14,6 +41,42 @@ pub trait SmartPointer<T: ?Sized>: Sealed {
     unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard);
 
     unsafe fn cast(base: *mut T) -> Self;
+
+    /// Allocates memory for `layout` and takes ownership of the value initialized there by `init`,
+    /// which returns the pointer to it.
+    unsafe fn new<F>(layout: Layout, init: F) -> Self
+    where
+        Self: Sized,
+        F: FnOnce(*mut u8) -> *mut T,
+    {
+        let (base, guard) = unsafe { Self::alloc(layout) };
+        let ptr = init(base);
+        mem::forget(guard);
+        unsafe { Self::cast(ptr) }
+    }
+}
+
+/// DST whose pointer metadata can be stored in the allocation by [`ThinBox`], [`ThinRc`] and [`ThinArc`].
+///
+/// Implemented by `#[derive(Dstify)]`. `Metadata` is the length of a `slice` DST, stored as `usize`
+/// or as the smallest integer fitting `#[dstify(max_len = ...)]`, and the whole wide pointer for a `dyn Trait` DST.
+///
+/// # Safety
+/// `from_metadata(base, metadata(ptr))` must return `ptr` with its address replaced by that of `base`.
+/// Unless `RELOCATABLE` is `true`, `base` may only point into the allocation `ptr` points to.
+pub unsafe trait ThinDst {
+    type Metadata: Copy;
+
+    /// Whether the pointer returned by `from_metadata` derives from `base` alone, so the value may be moved
+    /// to another allocation, which [`InlineDst`] does.
+    ///
+    /// `false` for `dyn Trait` DSTs, because on stable Rust the vtable can only be attached to the address of `base`,
+    /// keeping the provenance of the original pointer.
+    const RELOCATABLE: bool;
+
+    fn metadata(ptr: *const Self) -> Self::Metadata;
+
+    fn from_metadata(base: *mut u8, metadata: Self::Metadata) -> *mut Self;
 }
 
 pub struct DropGuard {
@@ -22,8 +85,18 @@ pub struct DropGuard {
 }
 impl Drop for DropGuard {
     fn drop(&mut self) {
-        if self.layout.size() != 0 {
-            unsafe { dealloc(self.base, self.layout) };
-        }
+        unsafe { crate::private::dealloc(self.base, self.layout) };
     }
 }
+
+/// Layout of the allocation holding `prefix` followed by `value` and the offset of `value` within it.
+///
+/// The prefix is stored right in front of the value, at `offset - prefix.size()`, which is always aligned,
+/// so it can be found from a pointer to the value alone.
+#[inline]
+fn prefixed(prefix: Layout, value: Layout) -> (Layout, usize) {
+    let (layout, offset) = prefix
+        .extend(value)
+        .expect("layout exceeds `isize::MAX` bytes");
+    (layout.pad_to_align(), offset)
+}
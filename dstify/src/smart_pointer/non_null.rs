@@ -0,0 +1,35 @@
+use super::{DropGuard, Sealed, SmartPointer};
+use alloc::{alloc::Layout, boxed::Box};
+use core::ptr::{self, NonNull};
+
+impl<T: ?Sized> Sealed for NonNull<T> {}
+
+/// Raw pointer to a value allocated like `Box<T>`, for passing DSTs across FFI boundaries.
+/// It must eventually be freed by [`dealloc`](crate::dealloc) or converted into `Box<T>` by `Box::from_raw`.
+impl<T: ?Sized> SmartPointer<T> for NonNull<T> {
+    type Guard = DropGuard;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        unsafe { Box::<T>::alloc(layout) }
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        unsafe { NonNull::new_unchecked(base) }
+    }
+}
+
+/// Drops the value behind `ptr` and frees its memory.
+///
+/// The layout of the allocation is that of the value: its fields laid out in order by `#[repr(C)]`,
+/// with the size rounded up to the alignment, which is the same layout `init_unsized` allocates.
+///
+/// # Safety
+/// `ptr` must come from `init_unsized` or `init_unsized_checked` returning `NonNull<T>`, or from `Box::into_raw`,
+/// and must not be used afterwards.
+pub unsafe fn dealloc<T: ?Sized>(ptr: NonNull<T>) {
+    let layout = Layout::for_value(unsafe { ptr.as_ref() });
+    unsafe {
+        ptr::drop_in_place(ptr.as_ptr());
+        crate::private::dealloc(ptr.as_ptr().cast(), layout);
+    }
+}
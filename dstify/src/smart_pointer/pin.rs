@@ -0,0 +1,36 @@
+use super::{Sealed, SmartPointer, UniqueRc};
+use alloc::{alloc::Layout, boxed::Box, rc::Rc};
+use core::pin::Pin;
+
+#[cfg(target_has_atomic = "ptr")]
+use super::UniqueArc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// Implements [`SmartPointer`] for `Pin<$ptr<T>>`, pinning the value right after it's initialized.
+///
+/// The value is initialized in the allocation of `$unique`, which `$ptr` takes over as is,
+/// so `Rc` and `Arc` are allocated with their counts in front rather than copied from a `Box`.
+macro_rules! pinned {
+    ($($ptr:ident => $unique:ident),*) => {$(
+        impl<T: ?Sized> Sealed for Pin<$ptr<T>> {}
+
+        impl<T: ?Sized> SmartPointer<T> for Pin<$ptr<T>> {
+            type Guard = <$unique<T> as SmartPointer<T>>::Guard;
+
+            unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+                unsafe { <$unique<T> as SmartPointer<T>>::alloc(layout) }
+            }
+
+            unsafe fn cast(base: *mut T) -> Self {
+                // SAFETY: the pointer is pinned before the value can be reached through it,
+                // and `$ptr` takes over the allocation the value was initialized in without moving it
+                unsafe { Pin::new_unchecked($ptr::from(<$unique<T> as SmartPointer<T>>::cast(base))) }
+            }
+        }
+    )*};
+}
+
+pinned!(Box => Box, Rc => UniqueRc);
+#[cfg(target_has_atomic = "ptr")]
+pinned!(Arc => UniqueArc);
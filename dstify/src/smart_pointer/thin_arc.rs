@@ -0,0 +1,143 @@
+use super::{
+    DropGuard, Sealed, SmartPointer, ThinDst,
+    thin_shared::{Shared, Weak},
+};
+use alloc::alloc::Layout;
+use core::{fmt, ops::Deref, sync::atomic::AtomicUsize};
+
+/// Thread-safe reference-counting pointer to a dynamically-sized `T`, that is only one pointer wide.
+///
+/// The reference counts and the [metadata](ThinDst::Metadata) of the pointer to `T` are stored in the allocation, right in front of `T` itself.
+///
+/// ```
+/// use dstify::{Dstify, ThinArc};
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Rec {
+///     id: u32,
+///     name: str,
+/// }
+///
+/// let rec: ThinArc<Rec> = Rec::init_unsized(1, "name");
+/// let other = rec.clone();
+/// assert!(ThinArc::ptr_eq(&rec, &other));
+/// assert_eq!(&other.name, "name");
+/// assert_eq!(size_of_val(&rec), size_of::<usize>());
+/// ```
+pub struct ThinArc<T: ?Sized + ThinDst>(Shared<T, AtomicUsize>);
+
+/// Non-owning reference to the value of a [`ThinArc`], obtained by [`ThinArc::downgrade`].
+pub struct WeakThinArc<T: ?Sized + ThinDst>(Weak<T, AtomicUsize>);
+
+impl<T: ?Sized + ThinDst> ThinArc<T> {
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.0.as_ptr()
+    }
+
+    /// Returns a mutable reference to the value, if there are no other `ThinArc` or [`WeakThinArc`] pointers to it.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        this.0.get_mut()
+    }
+
+    /// Returns `true` if both point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0.ptr_eq(&other.0)
+    }
+
+    #[inline]
+    pub fn downgrade(this: &Self) -> WeakThinArc<T> {
+        WeakThinArc(this.0.downgrade())
+    }
+
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.0.strong_count()
+    }
+
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        this.0.weak_count()
+    }
+}
+
+impl<T: ?Sized + ThinDst> WeakThinArc<T> {
+    /// Returns a new strong pointer, if the value wasn't dropped yet.
+    #[inline]
+    pub fn upgrade(&self) -> Option<ThinArc<T>> {
+        self.0.upgrade().map(ThinArc)
+    }
+
+    /// Returns `true` if both point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0.ptr_eq(&other.0)
+    }
+
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+}
+
+unsafe impl<T: ?Sized + ThinDst + Send + Sync> Send for ThinArc<T> {}
+unsafe impl<T: ?Sized + ThinDst + Send + Sync> Sync for ThinArc<T> {}
+unsafe impl<T: ?Sized + ThinDst + Send + Sync> Send for WeakThinArc<T> {}
+unsafe impl<T: ?Sized + ThinDst + Send + Sync> Sync for WeakThinArc<T> {}
+
+impl<T: ?Sized + ThinDst> Sealed for ThinArc<T> {}
+
+impl<T: ?Sized + ThinDst> SmartPointer<T> for ThinArc<T> {
+    type Guard = DropGuard;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        unsafe { Shared::<T, AtomicUsize>::alloc(layout) }
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        Self(unsafe { Shared::cast(base) })
+    }
+}
+
+impl<T: ?Sized + ThinDst> Clone for ThinArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + ThinDst> Clone for WeakThinArc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + ThinDst> Deref for ThinArc<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*self.0.as_ptr() }
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Debug> fmt::Debug for ThinArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Display> fmt::Display for ThinArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst> fmt::Debug for WeakThinArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
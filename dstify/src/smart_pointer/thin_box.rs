@@ -0,0 +1,121 @@
+use super::{DropGuard, Sealed, SmartPointer, ThinDst, prefixed};
+use alloc::alloc::{Layout, alloc as allocate, dealloc, handle_alloc_error};
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+/// Owning pointer to a dynamically-sized `T`, that is only one pointer wide.
+///
+/// The [metadata](ThinDst::Metadata) of the pointer to `T`, the length of a `slice` DST or the vtable of a `dyn Trait` DST,
+/// is stored in the allocation, right in front of `T` itself.
+/// Dereferencing therefore costs one extra read compared to [`Box`](alloc::boxed::Box).
+///
+/// ```
+/// use dstify::{Dstify, ThinBox};
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Rec {
+///     id: u32,
+///     name: str,
+/// }
+///
+/// let rec: ThinBox<Rec> = Rec::init_unsized(1, "name");
+/// assert_eq!(&rec.name, "name");
+/// assert_eq!(size_of_val(&rec), size_of::<usize>());
+/// ```
+pub struct ThinBox<T: ?Sized + ThinDst> {
+    /// points to `T`, preceded by its metadata
+    ptr: NonNull<u8>,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: ?Sized + ThinDst + Send> Send for ThinBox<T> {}
+unsafe impl<T: ?Sized + ThinDst + Sync> Sync for ThinBox<T> {}
+
+impl<T: ?Sized + ThinDst> ThinBox<T> {
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *mut T {
+        let base = this.ptr.as_ptr();
+        let metadata = unsafe { ptr::read(base.cast::<T::Metadata>().sub(1)) };
+        T::from_metadata(base, metadata)
+    }
+}
+
+impl<T: ?Sized + ThinDst> Sealed for ThinBox<T> {}
+
+impl<T: ?Sized + ThinDst> SmartPointer<T> for ThinBox<T> {
+    type Guard = DropGuard;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        let (layout, offset) = prefixed(Layout::new::<T::Metadata>(), layout);
+        let start = if layout.size() != 0 {
+            let start = unsafe { allocate(layout) };
+            if start.is_null() {
+                handle_alloc_error(layout);
+            }
+            start
+        } else {
+            ptr::without_provenance_mut(layout.align())
+        };
+        let guard = DropGuard {
+            base: start,
+            layout,
+        };
+        (unsafe { start.add(offset) }, guard)
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        unsafe { ptr::write(base.cast::<T::Metadata>().sub(1), T::metadata(base)) };
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(base.cast::<u8>()) },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst> Drop for ThinBox<T> {
+    fn drop(&mut self) {
+        let value = Self::as_ptr(self);
+        let (layout, offset) = prefixed(
+            Layout::new::<T::Metadata>(),
+            Layout::for_value(unsafe { &*value }),
+        );
+        unsafe {
+            ptr::drop_in_place(value);
+            if layout.size() != 0 {
+                dealloc(self.ptr.as_ptr().sub(offset), layout);
+            }
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst> Deref for ThinBox<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*Self::as_ptr(self) }
+    }
+}
+
+impl<T: ?Sized + ThinDst> DerefMut for ThinBox<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *Self::as_ptr(self) }
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Debug> fmt::Debug for ThinBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Display> fmt::Display for ThinBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
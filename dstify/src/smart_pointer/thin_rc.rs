@@ -0,0 +1,138 @@
+use super::{
+    DropGuard, Sealed, SmartPointer, ThinDst,
+    thin_shared::{Shared, Weak},
+};
+use alloc::alloc::Layout;
+use core::{cell::Cell, fmt, ops::Deref};
+
+/// Single-threaded reference-counting pointer to a dynamically-sized `T`, that is only one pointer wide.
+///
+/// The reference counts and the [metadata](ThinDst::Metadata) of the pointer to `T` are stored in the allocation, right in front of `T` itself.
+///
+/// ```
+/// use dstify::{Dstify, ThinRc};
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Rec {
+///     id: u32,
+///     name: str,
+/// }
+///
+/// let rec: ThinRc<Rec> = Rec::init_unsized(1, "name");
+/// let other = rec.clone();
+/// assert!(ThinRc::ptr_eq(&rec, &other));
+/// assert_eq!(&other.name, "name");
+/// assert_eq!(size_of_val(&rec), size_of::<usize>());
+/// ```
+pub struct ThinRc<T: ?Sized + ThinDst>(Shared<T, Cell<usize>>);
+
+/// Non-owning reference to the value of a [`ThinRc`], obtained by [`ThinRc::downgrade`].
+pub struct WeakThinRc<T: ?Sized + ThinDst>(Weak<T, Cell<usize>>);
+
+impl<T: ?Sized + ThinDst> ThinRc<T> {
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.0.as_ptr()
+    }
+
+    /// Returns a mutable reference to the value, if there are no other `ThinRc` or [`WeakThinRc`] pointers to it.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        this.0.get_mut()
+    }
+
+    /// Returns `true` if both point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0.ptr_eq(&other.0)
+    }
+
+    #[inline]
+    pub fn downgrade(this: &Self) -> WeakThinRc<T> {
+        WeakThinRc(this.0.downgrade())
+    }
+
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.0.strong_count()
+    }
+
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        this.0.weak_count()
+    }
+}
+
+impl<T: ?Sized + ThinDst> WeakThinRc<T> {
+    /// Returns a new strong pointer, if the value wasn't dropped yet.
+    #[inline]
+    pub fn upgrade(&self) -> Option<ThinRc<T>> {
+        self.0.upgrade().map(ThinRc)
+    }
+
+    /// Returns `true` if both point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0.ptr_eq(&other.0)
+    }
+
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+}
+
+impl<T: ?Sized + ThinDst> Sealed for ThinRc<T> {}
+
+impl<T: ?Sized + ThinDst> SmartPointer<T> for ThinRc<T> {
+    type Guard = DropGuard;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        unsafe { Shared::<T, Cell<usize>>::alloc(layout) }
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        Self(unsafe { Shared::cast(base) })
+    }
+}
+
+impl<T: ?Sized + ThinDst> Clone for ThinRc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + ThinDst> Clone for WeakThinRc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + ThinDst> Deref for ThinRc<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*self.0.as_ptr() }
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Debug> fmt::Debug for ThinRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst + fmt::Display> fmt::Display for ThinRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + ThinDst> fmt::Debug for WeakThinRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
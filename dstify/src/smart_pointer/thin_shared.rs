@@ -0,0 +1,325 @@
+use super::{DropGuard, ThinDst, prefixed};
+use alloc::alloc::{Layout, alloc as allocate, dealloc, handle_alloc_error};
+use core::{
+    cell::Cell,
+    marker::PhantomData,
+    ptr::{self, NonNull},
+};
+#[cfg(target_has_atomic = "ptr")]
+use core::{
+    hint,
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+
+/// Counts above this are treated as an overflow, the same limit `std` uses for `Rc` and `Arc`.
+const MAX_COUNT: usize = isize::MAX as usize;
+
+/// Reference count of [`Shared`], non-atomic for `ThinRc` and atomic for `ThinArc`.
+///
+/// The weak count is "locked" by setting it to `usize::MAX` while checking for uniqueness,
+/// so no `Weak` can be created from another strong reference in the meantime.
+pub trait Count {
+    fn new(count: usize) -> Self;
+    fn get(&self) -> usize;
+    /// Increments the count, unless it's locked.
+    fn increment(&self);
+    /// Increments the count, unless it's zero.
+    fn increment_nonzero(&self) -> bool;
+    /// Decrements the count, returning whether it dropped to zero.
+    fn decrement(&self) -> bool;
+    /// Locks the count if it's one.
+    fn lock(&self) -> bool;
+    fn unlock(&self);
+}
+
+#[cold]
+fn overflow() -> ! {
+    panic!("reference count overflow")
+}
+
+impl Count for Cell<usize> {
+    #[inline]
+    fn new(count: usize) -> Self {
+        Cell::new(count)
+    }
+    #[inline]
+    fn get(&self) -> usize {
+        Cell::get(self)
+    }
+    #[inline]
+    fn increment(&self) {
+        let count = Cell::get(self);
+        if count >= MAX_COUNT {
+            overflow();
+        }
+        self.set(count + 1);
+    }
+    #[inline]
+    fn increment_nonzero(&self) -> bool {
+        let count = Cell::get(self);
+        if count == 0 {
+            return false;
+        }
+        if count >= MAX_COUNT {
+            overflow();
+        }
+        self.set(count + 1);
+        true
+    }
+    #[inline]
+    fn decrement(&self) -> bool {
+        let count = Cell::get(self) - 1;
+        self.set(count);
+        count == 0
+    }
+    #[inline]
+    fn lock(&self) -> bool {
+        Cell::get(self) == 1
+    }
+    #[inline]
+    fn unlock(&self) {}
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl Count for AtomicUsize {
+    #[inline]
+    fn new(count: usize) -> Self {
+        AtomicUsize::new(count)
+    }
+    #[inline]
+    fn get(&self) -> usize {
+        self.load(Ordering::Acquire)
+    }
+    fn increment(&self) {
+        let mut count = self.load(Ordering::Relaxed);
+        loop {
+            if count == usize::MAX {
+                hint::spin_loop();
+                count = self.load(Ordering::Relaxed);
+                continue;
+            }
+            if count >= MAX_COUNT {
+                overflow();
+            }
+            match self.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(actual) => count = actual,
+            }
+        }
+    }
+    fn increment_nonzero(&self) -> bool {
+        let mut count = self.load(Ordering::Relaxed);
+        loop {
+            if count == 0 {
+                return false;
+            }
+            if count >= MAX_COUNT {
+                overflow();
+            }
+            match self.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => return true,
+                Err(actual) => count = actual,
+            }
+        }
+    }
+    #[inline]
+    fn decrement(&self) -> bool {
+        if self.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+    #[inline]
+    fn lock(&self) -> bool {
+        self.compare_exchange(1, usize::MAX, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+    #[inline]
+    fn unlock(&self) {
+        self.store(1, Ordering::Release);
+    }
+}
+
+/// Stored right in front of the value.
+///
+/// All the strong references together hold one weak reference, which keeps the allocation alive.
+/// The layout of the value is kept, because it can't be computed from the metadata once the value is dropped.
+#[repr(C)]
+struct Prefix<T: ?Sized + ThinDst, C> {
+    strong: C,
+    weak: C,
+    value: Layout,
+    metadata: T::Metadata,
+}
+
+impl<T: ?Sized + ThinDst, C> Prefix<T, C> {
+    #[inline]
+    fn value(this: NonNull<Self>) -> *mut T {
+        let metadata = unsafe { this.as_ref() }.metadata;
+        T::from_metadata(unsafe { this.add(1) }.cast::<u8>().as_ptr(), metadata)
+    }
+}
+
+/// Strong reference, the common part of `ThinRc` and `ThinArc`.
+pub struct Shared<T: ?Sized + ThinDst, C: Count> {
+    prefix: NonNull<Prefix<T, C>>,
+    _marker: PhantomData<T>,
+}
+
+/// Weak reference, the common part of `WeakThinRc` and `WeakThinArc`.
+pub struct Weak<T: ?Sized + ThinDst, C: Count> {
+    prefix: NonNull<Prefix<T, C>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Shared<T, C> {
+    pub unsafe fn alloc(layout: Layout) -> (*mut u8, DropGuard) {
+        let (layout, offset) = prefixed(Layout::new::<Prefix<T, C>>(), layout);
+        // never zero-sized, there's always the prefix
+        let start = unsafe { allocate(layout) };
+        if start.is_null() {
+            handle_alloc_error(layout);
+        }
+        (
+            unsafe { start.add(offset) },
+            DropGuard {
+                base: start,
+                layout,
+            },
+        )
+    }
+
+    pub unsafe fn cast(base: *mut T) -> Self {
+        let prefix = unsafe { base.cast::<Prefix<T, C>>().sub(1) };
+        let value = Prefix {
+            strong: C::new(1),
+            weak: C::new(1),
+            value: Layout::for_value(unsafe { &*base }),
+            metadata: T::metadata(base),
+        };
+        unsafe { ptr::write(prefix, value) };
+        Self {
+            prefix: unsafe { NonNull::new_unchecked(prefix) },
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn prefix(&self) -> &Prefix<T, C> {
+        unsafe { self.prefix.as_ref() }
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *mut T {
+        Prefix::value(self.prefix)
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        let prefix = self.prefix();
+        if !prefix.weak.lock() {
+            return None;
+        }
+        let unique = prefix.strong.get() == 1;
+        prefix.weak.unlock();
+        unique.then(|| unsafe { &mut *self.as_ptr() })
+    }
+
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix
+    }
+
+    pub fn downgrade(&self) -> Weak<T, C> {
+        self.prefix().weak.increment();
+        Weak {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.prefix().strong.get()
+    }
+
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        match self.prefix().weak.get() {
+            usize::MAX => 0,
+            count => count - 1,
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Clone for Shared<T, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.prefix().strong.increment();
+        Self {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Drop for Shared<T, C> {
+    fn drop(&mut self) {
+        if !self.prefix().strong.decrement() {
+            return;
+        }
+        unsafe { ptr::drop_in_place(self.as_ptr()) };
+        // release the weak reference held by the strong ones
+        drop(Weak {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        });
+    }
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Weak<T, C> {
+    #[inline]
+    fn prefix(&self) -> &Prefix<T, C> {
+        unsafe { self.prefix.as_ref() }
+    }
+
+    pub fn upgrade(&self) -> Option<Shared<T, C>> {
+        self.prefix().strong.increment_nonzero().then(|| Shared {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix
+    }
+
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.prefix().strong.get()
+    }
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Clone for Weak<T, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.prefix().weak.increment();
+        Self {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + ThinDst, C: Count> Drop for Weak<T, C> {
+    fn drop(&mut self) {
+        if self.prefix().weak.decrement() {
+            let (layout, offset) = prefixed(Layout::new::<Prefix<T, C>>(), self.prefix().value);
+            let value = unsafe { self.prefix.add(1) }.cast::<u8>().as_ptr();
+            unsafe { dealloc(value.sub(offset), layout) };
+        }
+    }
+}
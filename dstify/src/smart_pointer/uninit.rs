@@ -0,0 +1,106 @@
+use alloc::{alloc::Layout, rc::Rc};
+use core::{marker::PhantomData, mem::MaybeUninit, ptr};
+
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// `Rc` or `Arc`, whose uninitialized slices are handed out as raw memory for `UniqueRc` and `UniqueArc`.
+///
+/// The memory for a value of layout `layout` is allocated as `[A; layout.size() / layout.align()]`, where `A` has both
+/// the size and the alignment of `layout.align()`, so that the value can later be owned by `Rc<T>` or `Arc<T>`.
+///
+/// That the counts are then found in front of the value relies on the safety contract of
+/// [`Rc::from_raw`](alloc::rc::Rc::from_raw) and [`Arc::from_raw`](alloc::sync::Arc::from_raw): a pointer
+/// from `Rc<U>::into_raw` with unsized `U` may be converted into `Rc<T>`, if the data pointer of `U`
+/// has the same size and alignment as `T`, which `[A]` has.
+pub trait UninitShared {
+    /// Returns a pointer to uninitialized `[A; len]` owned by a strong reference.
+    fn new_uninit<A>(len: usize) -> *mut u8;
+
+    /// # Safety
+    /// `base` must come from `new_uninit::<A>(len)`. Nothing is dropped.
+    unsafe fn free<A>(base: *mut u8, len: usize);
+}
+
+impl<T: ?Sized> UninitShared for Rc<T> {
+    fn new_uninit<A>(len: usize) -> *mut u8 {
+        Rc::into_raw(Rc::<[A]>::new_uninit_slice(len))
+            .cast::<u8>()
+            .cast_mut()
+    }
+
+    unsafe fn free<A>(base: *mut u8, len: usize) {
+        let slice = ptr::slice_from_raw_parts(base.cast::<MaybeUninit<A>>(), len);
+        drop(unsafe { Rc::from_raw(slice) });
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: ?Sized> UninitShared for Arc<T> {
+    fn new_uninit<A>(len: usize) -> *mut u8 {
+        Arc::into_raw(Arc::<[A]>::new_uninit_slice(len))
+            .cast::<u8>()
+            .cast_mut()
+    }
+
+    unsafe fn free<A>(base: *mut u8, len: usize) {
+        let slice = ptr::slice_from_raw_parts(base.cast::<MaybeUninit<A>>(), len);
+        drop(unsafe { Arc::from_raw(slice) });
+    }
+}
+
+/// Evaluates `$body` with `$a` being a type, whose size and alignment are `$align`.
+///
+/// Covers every alignment up to 2^29, the largest one a type can have, so any `T` can be allocated.
+macro_rules! with_aligned {
+    ($align:expr, $a:ident => $body:expr) => {
+        with_aligned!(@ $align, $a => $body;
+            0x1 0x2 0x4 0x8 0x10 0x20 0x40 0x80 0x100 0x200 0x400 0x800 0x1000 0x2000 0x4000 0x8000
+            0x1_0000 0x2_0000 0x4_0000 0x8_0000 0x10_0000 0x20_0000 0x40_0000 0x80_0000
+            0x100_0000 0x200_0000 0x400_0000 0x800_0000 0x1000_0000 0x2000_0000)
+    };
+    (@ $align:expr, $a:ident => $body:expr; $($n:literal)*) => {
+        match $align {
+            $($n => {
+                #[allow(dead_code)]
+                #[repr(align($n))]
+                struct $a([u8; $n]);
+                $body
+            })*
+            align => panic!("alignment {align} is not supported"),
+        }
+    };
+}
+
+pub fn alloc<P: UninitShared>(layout: Layout) -> (*mut u8, UninitGuard<P>) {
+    let len = layout.size() / layout.align();
+    let base = with_aligned!(layout.align(), A => P::new_uninit::<A>(len));
+    let guard = UninitGuard {
+        base,
+        layout,
+        _marker: PhantomData,
+    };
+    (base, guard)
+}
+
+/// Frees the memory from [`alloc`] if the initialization panics.
+pub struct UninitGuard<P: UninitShared> {
+    base: *mut u8,
+    layout: Layout,
+    _marker: PhantomData<P>,
+}
+
+/// Frees the memory from [`alloc`] without dropping its contents.
+///
+/// # Safety
+/// `base` must come from `alloc::<P>(layout)`.
+pub unsafe fn free<P: UninitShared>(base: *mut u8, layout: Layout) {
+    let len = layout.size() / layout.align();
+    with_aligned!(layout.align(), A => unsafe { P::free::<A>(base, len) });
+}
+
+impl<P: UninitShared> Drop for UninitGuard<P> {
+    fn drop(&mut self) {
+        unsafe { free::<P>(self.base, self.layout) };
+    }
+}
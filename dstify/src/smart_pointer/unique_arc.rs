@@ -0,0 +1,104 @@
+use super::{
+    Sealed, SmartPointer,
+    uninit::{self, UninitGuard},
+};
+use alloc::{alloc::Layout, sync::Arc};
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// Uniquely owned `T` in an allocation of [`Arc`](alloc::sync::Arc), which can be mutated and then converted into `Arc<T>` without copying.
+///
+/// ```
+/// use dstify::{Dstify, UniqueArc};
+/// use std::sync::Arc;
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Packet {
+///     checksum: u8,
+///     payload: [u8],
+/// }
+///
+/// let mut packet: UniqueArc<Packet> = Packet::init_unsized(0, &[1, 2, 3]);
+/// packet.checksum = packet.payload.iter().fold(0, |sum, byte| sum ^ byte);
+/// let packet: Arc<Packet> = UniqueArc::into_arc(packet);
+/// assert_eq!(packet.checksum, 0);
+/// ```
+pub struct UniqueArc<T: ?Sized> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for UniqueArc<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for UniqueArc<T> {}
+
+impl<T: ?Sized> UniqueArc<T> {
+    /// Converts into `Arc<T>`, reusing the allocation.
+    #[inline]
+    pub fn into_arc(this: Self) -> Arc<T> {
+        let this = ManuallyDrop::new(this);
+        unsafe { Arc::from_raw(this.ptr.as_ptr()) }
+    }
+}
+
+impl<T: ?Sized> From<UniqueArc<T>> for Arc<T> {
+    #[inline]
+    fn from(unique: UniqueArc<T>) -> Self {
+        UniqueArc::into_arc(unique)
+    }
+}
+
+impl<T: ?Sized> Sealed for UniqueArc<T> {}
+
+impl<T: ?Sized> SmartPointer<T> for UniqueArc<T> {
+    type Guard = UninitGuard<Arc<T>>;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        uninit::alloc(layout)
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(base) },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for UniqueArc<T> {
+    fn drop(&mut self) {
+        drop(unsafe { Arc::from_raw(self.ptr.as_ptr()) });
+    }
+}
+
+impl<T: ?Sized> Deref for UniqueArc<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for UniqueArc<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for UniqueArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for UniqueArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
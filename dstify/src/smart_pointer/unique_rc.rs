@@ -0,0 +1,101 @@
+use super::{
+    Sealed, SmartPointer,
+    uninit::{self, UninitGuard},
+};
+use alloc::{alloc::Layout, rc::Rc};
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// Uniquely owned `T` in an allocation of [`Rc`](alloc::rc::Rc), which can be mutated and then converted into `Rc<T>` without copying.
+///
+/// ```
+/// use dstify::{Dstify, UniqueRc};
+/// use std::rc::Rc;
+///
+/// #[derive(Dstify)]
+/// #[repr(C)]
+/// struct Packet {
+///     checksum: u8,
+///     payload: [u8],
+/// }
+///
+/// let mut packet: UniqueRc<Packet> = Packet::init_unsized(0, &[1, 2, 3]);
+/// packet.checksum = packet.payload.iter().fold(0, |sum, byte| sum ^ byte);
+/// let packet: Rc<Packet> = UniqueRc::into_rc(packet);
+/// assert_eq!(packet.checksum, 0);
+/// ```
+pub struct UniqueRc<T: ?Sized> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ?Sized> UniqueRc<T> {
+    /// Converts into `Rc<T>`, reusing the allocation.
+    #[inline]
+    pub fn into_rc(this: Self) -> Rc<T> {
+        let this = ManuallyDrop::new(this);
+        unsafe { Rc::from_raw(this.ptr.as_ptr()) }
+    }
+}
+
+impl<T: ?Sized> From<UniqueRc<T>> for Rc<T> {
+    #[inline]
+    fn from(unique: UniqueRc<T>) -> Self {
+        UniqueRc::into_rc(unique)
+    }
+}
+
+impl<T: ?Sized> Sealed for UniqueRc<T> {}
+
+impl<T: ?Sized> SmartPointer<T> for UniqueRc<T> {
+    type Guard = UninitGuard<Rc<T>>;
+
+    unsafe fn alloc(layout: Layout) -> (*mut u8, Self::Guard) {
+        uninit::alloc(layout)
+    }
+
+    unsafe fn cast(base: *mut T) -> Self {
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(base) },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for UniqueRc<T> {
+    fn drop(&mut self) {
+        drop(unsafe { Rc::from_raw(self.ptr.as_ptr()) });
+    }
+}
+
+impl<T: ?Sized> Deref for UniqueRc<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for UniqueRc<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for UniqueRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for UniqueRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
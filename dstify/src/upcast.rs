@@ -0,0 +1,34 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+
+/// Conversion to another DST with the same normal fields, whose last field is a supertrait object of this one's,
+/// reusing the allocation.
+///
+/// Implemented by `#[dstify(upcast_to(U))]`.
+///
+/// # Safety
+/// `upcast_raw` must return a pointer to the same address, with metadata describing the same last field.
+pub unsafe trait Upcast<U: ?Sized> {
+    /// # Safety
+    /// `this` must point to a valid value of `Self`.
+    unsafe fn upcast_raw(this: *mut Self) -> *mut U;
+
+    fn upcast_ref(&self) -> &U {
+        unsafe { &*Self::upcast_raw(self as *const Self as *mut Self) }
+    }
+
+    fn upcast_mut(&mut self) -> &mut U {
+        unsafe { &mut *Self::upcast_raw(self) }
+    }
+
+    fn upcast_box(this: Box<Self>) -> Box<U> {
+        unsafe { Box::from_raw(Self::upcast_raw(Box::into_raw(this))) }
+    }
+
+    fn upcast_rc(this: Rc<Self>) -> Rc<U> {
+        unsafe { Rc::from_raw(Self::upcast_raw(Rc::into_raw(this) as *mut Self)) }
+    }
+
+    fn upcast_arc(this: Arc<Self>) -> Arc<U> {
+        unsafe { Arc::from_raw(Self::upcast_raw(Arc::into_raw(this) as *mut Self)) }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote, spanned::Spanned};
+
+/// `Borrow<Tail>` for `Box<Self>` plus `Hash`/`Eq`/`Ord` comparing only the tail,
+/// so that the `Borrow` contract holds.
+pub fn derive(dst: &Dst) -> Result<TokenStream, TokenStream> {
+    if let Type::TraitObject(trait_object) = dst.tail.ty {
+        return Err(syn::Error::new(
+            trait_object.span(),
+            "`#[dstify(borrow_tail)]` requires a `slice` DST last field",
+        )
+        .into_compile_error());
+    }
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let dst_field_ty = dst.tail.ty;
+    Ok(parse_quote! {
+        impl #impl_generics ::core::borrow::Borrow<#dst_field_ty> for ::dstify::private::Box<#name #ty_generics> #where_clause {
+            fn borrow(&self) -> &#dst_field_ty {
+                &self.#field
+            }
+        }
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                self.#field == other.#field
+            }
+        }
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&self.#field, &other.#field)
+            }
+        }
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                ::core::hash::Hash::hash(&self.#field, state)
+            }
+        }
+    })
+}
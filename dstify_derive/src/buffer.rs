@@ -0,0 +1,136 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Ident, Type, parse_quote, spanned::Spanned};
+
+/// Growable companion of a DST with `[T]` last field, with spare capacity for the last field.
+pub fn derive(dst: &Dst, buffer: &Ident) -> Result<TokenStream, TokenStream> {
+    let Type::Slice(slice) = dst.tail.ty else {
+        return Err(syn::Error::new(
+            dst.tail.ty.span(),
+            "`#[dstify(buffer = \"...\")]` requires a `[T]` last field",
+        )
+        .into_compile_error());
+    };
+    let elem = &slice.elem;
+    let input = dst.input;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let doc = format!(
+        "Growable [`{name}`], with spare capacity for the last field. Freezes into `Box<{name}>`, `Rc<{name}>` or `Arc<{name}>` \
+         using [`{buffer}::into_box`], [`{buffer}::into_rc`] or [`{buffer}::into_arc`]."
+    );
+
+    let n = dst.header.len();
+    let args = dst
+        .header
+        .iter()
+        .map(|field| -> TokenStream {
+            let ident = &field.ident;
+            let ty = field.ty;
+            parse_quote!(#ident: #ty)
+        })
+        .collect::<Vec<_>>();
+    let idents = dst
+        .header
+        .iter()
+        .map(|field| &field.ident)
+        .collect::<Vec<_>>();
+    let layouts = dst.header.iter().map(|field| -> TokenStream {
+        let ty = field.ty;
+        parse_quote!(::core::alloc::Layout::new::<#ty>())
+    });
+    let inits = dst.header.iter().map(|field| -> TokenStream {
+        let ident = &field.ident;
+        let value = field.encode(parse_quote!(#ident));
+        parse_quote!(::core::ptr::write(<*mut _>::cast(offsets.get_next()), #value))
+    });
+
+    Ok(parse_quote! {
+        #[doc = #doc]
+        #vis struct #buffer #generics #where_clause {
+            buf: ::dstify::private::Buf<#name #ty_generics, #elem, #n>,
+        }
+
+        impl #impl_generics #buffer #ty_generics #where_clause {
+            fn new(#(#args,)*) -> Self {
+                Self::with_capacity(#(#idents,)* 0)
+            }
+            fn with_capacity(#(#args,)* capacity: usize) -> Self {
+                Self::with_capacity_in::<::dstify::private::Box<_>>(#(#idents,)* capacity)
+            }
+            /// Allocates like `R`, so that freezing into `R` doesn't copy when the capacity equals the length.
+            fn with_capacity_in<R>(#(#args,)* capacity: usize) -> Self
+            where
+                R: ::dstify::private::BufStorage<#name #ty_generics>,
+            {
+                let buf = unsafe {
+                    ::dstify::private::Buf::new(R::STORAGE, [#(#layouts),*], capacity, |base, len| {
+                        // this cast must remain here, cannot be done using generics
+                        ::core::ptr::slice_from_raw_parts_mut(base, len) as *mut #name #ty_generics
+                    }, |offsets| {
+                        #(#inits;)*
+                    })
+                };
+                Self { buf }
+            }
+            fn len(&self) -> usize {
+                self.buf.len()
+            }
+            fn is_empty(&self) -> bool {
+                self.buf.is_empty()
+            }
+            fn capacity(&self) -> usize {
+                self.buf.capacity()
+            }
+            fn reserve(&mut self, additional: usize) {
+                self.buf.reserve(additional)
+            }
+            fn shrink_to_fit(&mut self) {
+                self.buf.shrink_to_fit()
+            }
+            fn push(&mut self, value: #elem) {
+                self.buf.push(value)
+            }
+            fn extend_from_slice(&mut self, values: &[#elem]) {
+                self.buf.extend_from_slice(values)
+            }
+            /// Reuses the allocation of a buffer allocated like `Box`, which is shrunk to fit first, otherwise copies.
+            fn into_box(self) -> ::dstify::private::Box<#name #ty_generics> {
+                self.buf.into_box()
+            }
+            /// Reuses the allocation of a buffer allocated like `Rc` whose capacity equals its length, otherwise copies.
+            fn into_rc(self) -> ::dstify::private::Rc<#name #ty_generics> {
+                self.buf.into_rc()
+            }
+            /// Reuses the allocation of a buffer allocated like `Arc` whose capacity equals its length, otherwise copies.
+            fn into_arc(self) -> ::dstify::private::Arc<#name #ty_generics> {
+                self.buf.into_arc()
+            }
+        }
+
+        impl #impl_generics ::core::ops::Deref for #buffer #ty_generics #where_clause {
+            type Target = #name #ty_generics;
+            fn deref(&self) -> &Self::Target {
+                unsafe { &*self.buf.as_ptr() }
+            }
+        }
+
+        impl #impl_generics ::core::ops::DerefMut for #buffer #ty_generics #where_clause {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                unsafe { &mut *self.buf.as_ptr() }
+            }
+        }
+
+        impl #impl_generics ::core::iter::Extend<#elem> for #buffer #ty_generics #where_clause {
+            fn extend<I: ::core::iter::IntoIterator<Item = #elem>>(&mut self, iter: I) {
+                let iter = ::core::iter::IntoIterator::into_iter(iter);
+                self.buf.reserve(::core::iter::Iterator::size_hint(&iter).0);
+                for value in iter {
+                    self.buf.push(value);
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,170 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Expr, ExprLit, Lit, Member, Type, parse_quote, spanned::Spanned};
+
+/// `C_DECLARATION`, the C declaration of the struct with a flexible array member for the last field.
+pub fn derive(dst: &Dst) -> Result<TokenStream, TokenStream> {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    if dst.header.is_empty() {
+        return Err(error(
+            name.span(),
+            "`#[dstify(c_declaration)]` requires at least one field before the last one",
+        ));
+    }
+    let mut declaration = format!("struct {name} {{\n");
+    let mut pointees = Vec::new();
+    for field in &dst.header {
+        let member = c_member(&field.member);
+        let field = declarator(field.ty, member, &mut pointees)?;
+        declaration += &format!("    {field};\n");
+    }
+    let member = format!("{}[]", c_member(&dst.tail.member));
+    let tail = match dst.tail.ty {
+        Type::Slice(slice) => declarator(&slice.elem, member, &mut pointees).ok(),
+        Type::Path(path) if path.path.is_ident("str") => Some(format!("uint8_t {member}")),
+        Type::Path(path) if path.path.segments.last().unwrap().ident == "CStr" => {
+            Some(format!("char {member}"))
+        }
+        _ => None,
+    };
+    let Some(tail) = tail else {
+        return Err(error(
+            dst.tail.ty.span(),
+            "`#[dstify(c_declaration)]` requires a `slice` DST last field of a type representable in C",
+        ));
+    };
+    declaration += &format!("    {tail};\n}};\n");
+
+    Ok(parse_quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            const C_DECLARATION: &'static str = {
+                // pointers to unsized types are fat in Rust, unlike their `void *` declarations
+                #(::dstify::private::assert_sized::<#pointees>();)*
+                #declaration
+            };
+        }
+    })
+}
+
+fn error(span: proc_macro2::Span, message: &str) -> TokenStream {
+    syn::Error::new(span, message).into_compile_error()
+}
+
+/// Tuple struct fields are named `_0`, `_1`, ...
+fn c_member(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => format!("_{}", index.index),
+    }
+}
+
+/// Declaration of `name` with type `ty`, e.g. `uint8_t name[4]`, if `ty` has a C equivalent.
+///
+/// Pointees that have to be checked to be `Sized` are added to `pointees`.
+fn declarator<'a>(
+    ty: &'a Type,
+    name: String,
+    pointees: &mut Vec<&'a Type>,
+) -> Result<String, TokenStream> {
+    let unsupported = || error(ty.span(), "this type can't be represented in C");
+    match ty {
+        Type::Paren(paren) => declarator(&paren.elem, name, pointees),
+        Type::Array(array) => {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Int(len), ..
+            }) = &array.len
+            else {
+                return Err(unsupported());
+            };
+            declarator(
+                &array.elem,
+                format!("{name}[{}]", len.base10_digits()),
+                pointees,
+            )
+        }
+        Type::Ptr(ptr) => {
+            if is_unsized(&ptr.elem) {
+                return Err(error(
+                    ptr.elem.span(),
+                    "pointers to unsized types can't be represented in C, they carry metadata",
+                ));
+            }
+            // pointers to anything but primitives become `void *`
+            let pointee = primitive(&ptr.elem).unwrap_or_else(|| {
+                pointees.push(&ptr.elem);
+                "void"
+            });
+            let qualifier = if ptr.const_token.is_some() {
+                "const "
+            } else {
+                ""
+            };
+            Ok(format!("{qualifier}{pointee} *{name}"))
+        }
+        _ => match primitive(ty) {
+            // `void` only exists behind pointers
+            Some("void") | None => Err(unsupported()),
+            Some(c_type) => Ok(format!("{c_type} {name}")),
+        },
+    }
+}
+
+/// Types known to be unsized, others are checked by `assert_sized` in the generated code.
+fn is_unsized(ty: &Type) -> bool {
+    match ty {
+        Type::Paren(paren) => is_unsized(&paren.elem),
+        Type::Slice(_) | Type::TraitObject(_) => true,
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|segment| {
+                ["str", "CStr", "OsStr", "Path"]
+                    .iter()
+                    .any(|name| segment.ident == name)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// C type of primitive Rust types and the `core::ffi` aliases, recognized by name.
+fn primitive(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    if !segment.arguments.is_none() {
+        return None;
+    }
+    Some(match segment.ident.to_string().as_str() {
+        "u8" => "uint8_t",
+        "u16" => "uint16_t",
+        "u32" => "uint32_t",
+        "u64" => "uint64_t",
+        "i8" => "int8_t",
+        "i16" => "int16_t",
+        "i32" => "int32_t",
+        "i64" => "int64_t",
+        "usize" => "size_t",
+        "isize" => "ptrdiff_t",
+        "f32" | "c_float" => "float",
+        "f64" | "c_double" => "double",
+        "bool" => "bool",
+        "char" => "uint32_t",
+        "c_char" => "char",
+        "c_schar" => "signed char",
+        "c_uchar" => "unsigned char",
+        "c_short" => "short",
+        "c_ushort" => "unsigned short",
+        "c_int" => "int",
+        "c_uint" => "unsigned int",
+        "c_long" => "long",
+        "c_ulong" => "unsigned long",
+        "c_longlong" => "long long",
+        "c_ulonglong" => "unsigned long long",
+        "c_void" => "void",
+        _ => return None,
+    })
+}
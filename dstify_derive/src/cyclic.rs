@@ -0,0 +1,88 @@
+use crate::{Dst, dyn_bounds, sized_twin};
+use proc_macro2::{Span, TokenStream};
+use syn::{Ident, Type, parse_quote, spanned::Spanned};
+
+/// `init_unsized_cyclic`, constructing a private sized twin of the DST with `Rc::new_cyclic` or `Arc::new_cyclic`,
+/// which is then converted into the DST in place.
+///
+/// Only sized last fields have a twin, so `[T]` last fields are given as `[T; N]`.
+pub fn derive(dst: &Dst) -> Result<TokenStream, TokenStream> {
+    let input = dst.input;
+    let name = &input.ident;
+    let twin = Ident::new("__DstifyCyclic", Span::call_site());
+    let mut twin_generics = input.generics.clone();
+    twin_generics.params.push(parse_quote!(__T));
+    let twin_where_clause = &twin_generics.where_clause;
+    // the parameters may be used only by the last field
+    let marker = sized_twin::marker(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let header_ty = dst.header.iter().map(|field| field.ty).collect::<Vec<_>>();
+    let header_ident = dst
+        .header
+        .iter()
+        .map(|field| &field.ident)
+        .collect::<Vec<_>>();
+    let header_value = dst
+        .header
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+            field.encode(parse_quote!(#ident))
+        })
+        .collect::<Vec<TokenStream>>();
+    let tail_ident = &dst.tail.ident;
+    let (generics, tail_ty, cast): (TokenStream, Type, TokenStream) = match dst.tail.ty {
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            (
+                parse_quote!(<R, const N: usize>),
+                parse_quote!([#elem; N]),
+                parse_quote!(|ptr| ::core::ptr::slice_from_raw_parts(ptr.cast::<#elem>(), N) as *const Self),
+            )
+        }
+        Type::TraitObject(trait_object) => {
+            let bounds = dyn_bounds(trait_object);
+            (
+                parse_quote!(<R, D: #bounds>),
+                parse_quote!(D),
+                parse_quote!(|ptr| ptr.cast::<D>() as *const (dyn #bounds) as *const Self),
+            )
+        }
+        ty => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "`#[dstify(cyclic)]` requires a `[T]` or `dyn Trait` last field",
+            )
+            .into_compile_error());
+        }
+    };
+
+    Ok(parse_quote! {
+        const _: () = {
+            #[repr(C)]
+            struct #twin #twin_generics (#(#header_ty,)* __T, #marker) #twin_where_clause;
+
+            impl #impl_generics #name #ty_generics #where_clause {
+                fn init_unsized_cyclic #generics(
+                    init: impl ::core::ops::FnOnce(&<R as ::dstify::Cyclic<Self>>::Weak) -> (#(#header_ty,)*),
+                    #tail_ident: #tail_ty,
+                ) -> R
+                where
+                    R: ::dstify::Cyclic<Self>,
+                {
+                    // SAFETY: the twin has the same fields as `Self`, so `Self` holding the same last field has the same layout
+                    unsafe {
+                        R::new_cyclic(
+                            |weak| {
+                                let (#(#header_ident,)*) = init(weak);
+                                #twin(#(#header_value,)* #tail_ident, ::core::marker::PhantomData)
+                            },
+                            #cast,
+                        )
+                    }
+                }
+            }
+        };
+    })
+}
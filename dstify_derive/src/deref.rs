@@ -0,0 +1,33 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::parse_quote;
+
+/// `Deref<Target = Tail>` and `AsRef<U>` for every `U` the tail itself can be referenced as.
+pub fn derive(dst: &Dst) -> TokenStream {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let dst_field_ty = dst.tail.ty;
+
+    let mut as_ref_generics = dst.input.generics.clone();
+    as_ref_generics.params.push(parse_quote!(__U: ?Sized));
+    as_ref_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#dst_field_ty: ::core::convert::AsRef<__U>));
+    let (as_ref_impl_generics, _, as_ref_where_clause) = as_ref_generics.split_for_impl();
+
+    parse_quote! {
+        impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #dst_field_ty;
+            fn deref(&self) -> &Self::Target {
+                &self.#field
+            }
+        }
+        impl #as_ref_impl_generics ::core::convert::AsRef<__U> for #name #ty_generics #as_ref_where_clause {
+            fn as_ref(&self) -> &__U {
+                ::core::convert::AsRef::as_ref(&self.#field)
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{Dst, Field};
+use proc_macro2::TokenStream;
+use syn::{Attribute, Ident, LitStr, Member, Type, parse_quote};
+
+/// Byte order of a header field, see `#[dstify(endian = "...")]`.
+#[derive(Clone, Copy)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    pub fn parse(value: LitStr) -> syn::Result<Self> {
+        match value.value().as_str() {
+            "le" => Ok(Endian::Little),
+            "be" => Ok(Endian::Big),
+            _ => Err(syn::Error::new(
+                value.span(),
+                "expected `\"le\"` or `\"be\"`",
+            )),
+        }
+    }
+
+    /// Byte order of a field given by its `#[dstify(le)]` or `#[dstify(be)]` attribute,
+    /// or by the struct's `endian` for primitive integer fields.
+    pub fn of_field(
+        attrs: &[Attribute],
+        ty: &Type,
+        default: Option<Endian>,
+    ) -> Result<Option<Self>, TokenStream> {
+        let mut endian = None;
+        for attr in attrs {
+            if !attr.path().is_ident("dstify") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("le") {
+                    endian = Some(Endian::Little);
+                    Ok(())
+                } else if meta.path.is_ident("be") {
+                    endian = Some(Endian::Big);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `dstify` field attribute"))
+                }
+            })
+            .map_err(syn::Error::into_compile_error)?;
+        }
+        Ok(endian.or(default.filter(|_| is_integer(ty))))
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    const INTEGERS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    matches!(ty, Type::Path(path) if INTEGERS.iter().any(|int| path.path.is_ident(int)))
+}
+
+impl Field<'_> {
+    /// Converts `value` from the native byte order into the stored one.
+    pub fn encode(&self, value: TokenStream) -> TokenStream {
+        let ty = self.ty;
+        match self.endian {
+            Some(Endian::Little) => parse_quote!(<#ty>::to_le(#value)),
+            Some(Endian::Big) => parse_quote!(<#ty>::to_be(#value)),
+            None => value,
+        }
+    }
+
+    /// Converts the stored `value` into the native byte order.
+    pub fn decode(&self, value: TokenStream) -> TokenStream {
+        let ty = self.ty;
+        match self.endian {
+            Some(Endian::Little) => parse_quote!(<#ty>::from_le(#value)),
+            Some(Endian::Big) => parse_quote!(<#ty>::from_be(#value)),
+            None => value,
+        }
+    }
+}
+
+/// Getters and setters of the fields with a byte order, converting from and to the native one.
+pub fn accessors(dst: &Dst) -> TokenStream {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let accessors = dst
+        .header
+        .iter()
+        .filter(|field| field.endian.is_some())
+        .map(|field| -> TokenStream {
+            let member = &field.member;
+            let ty = field.ty;
+            let getter = match member {
+                Member::Named(ident) => ident.clone(),
+                Member::Unnamed(index) => Ident::new(&format!("field_{}", index.index), index.span),
+            };
+            let setter = Ident::new(&format!("set_{getter}"), getter.span());
+            let get = field.decode(parse_quote!(self.#member));
+            let set = field.encode(parse_quote!(value));
+            parse_quote! {
+                fn #getter(&self) -> #ty {
+                    #get
+                }
+                fn #setter(&mut self, value: #ty) {
+                    self.#member = #set;
+                }
+            }
+        });
+    parse_quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{LitStr, Member, Token, meta::ParseNestedMeta, parse_quote};
+
+/// Trait implemented for the struct by delegating to its last field.
+pub enum Forward {
+    Debug,
+    /// optional format string written before the last field, may refer to the other fields by name
+    Display(Option<LitStr>),
+    Error,
+    Iterator,
+    Read,
+    Write,
+    Future,
+}
+
+impl Forward {
+    pub fn parse(meta: ParseNestedMeta) -> syn::Result<Self> {
+        let Some(last) = meta.path.segments.last() else {
+            return Err(meta.error("expected trait name"));
+        };
+        let forward = match last.ident.to_string().as_str() {
+            "Debug" => Forward::Debug,
+            "Display" => {
+                let prefix = if meta.input.peek(Token![=]) {
+                    Some(meta.value()?.parse()?)
+                } else {
+                    None
+                };
+                Forward::Display(prefix)
+            }
+            "Error" => Forward::Error,
+            "Iterator" => Forward::Iterator,
+            "Read" => Forward::Read,
+            "Write" => Forward::Write,
+            "Future" => Forward::Future,
+            _ => {
+                return Err(meta.error(
+                    "unsupported trait, expected one of `Debug`, `Display`, `Error`, `Iterator`, `io::Read`, `io::Write` or `Future`",
+                ));
+            }
+        };
+        Ok(forward)
+    }
+}
+
+pub fn derive(dst: &Dst, forwards: &[Forward]) -> TokenStream {
+    let impls = forwards.iter().map(|forward| derive_one(dst, forward));
+    parse_quote!(#(#impls)*)
+}
+
+fn derive_one(dst: &Dst, forward: &Forward) -> TokenStream {
+    let name = &dst.input.ident;
+    let field = &dst.tail.member;
+    let dst_field_ty = dst.tail.ty;
+
+    let (bound, body): (TokenStream, TokenStream) = match forward {
+        Forward::Debug => (
+            parse_quote!(::core::fmt::Debug),
+            parse_quote! {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Debug::fmt(&self.#field, f)
+                }
+            },
+        ),
+        Forward::Display(prefix) => {
+            let prefix = prefix.as_ref().map(|prefix| -> TokenStream {
+                let bindings = dst.header.iter().map(|header| -> TokenStream {
+                    let member = &header.member;
+                    // the field name, not the `init_unsized` argument, which may be renamed
+                    let ident = match member {
+                        Member::Named(ident) => ident,
+                        Member::Unnamed(_) => &header.ident,
+                    };
+                    let value = header.decode(parse_quote!(self.#member));
+                    parse_quote! {
+                        #[allow(unused_variables)]
+                        let #ident = &#value;
+                    }
+                });
+                parse_quote! {
+                    #(#bindings)*
+                    ::core::write!(__dstify_f, #prefix)?;
+                }
+            });
+            (
+                parse_quote!(::core::fmt::Display),
+                parse_quote! {
+                    fn fmt(&self, __dstify_f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        #prefix
+                        ::core::fmt::Display::fmt(&self.#field, __dstify_f)
+                    }
+                },
+            )
+        }
+        Forward::Error => (
+            parse_quote!(::core::error::Error),
+            parse_quote! {
+                fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                    ::core::error::Error::source(&self.#field)
+                }
+            },
+        ),
+        Forward::Iterator => (
+            parse_quote!(::core::iter::Iterator),
+            parse_quote! {
+                type Item = <#dst_field_ty as ::core::iter::Iterator>::Item;
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                    ::core::iter::Iterator::next(&mut self.#field)
+                }
+                fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                    ::core::iter::Iterator::size_hint(&self.#field)
+                }
+            },
+        ),
+        Forward::Read => (
+            parse_quote!(::std::io::Read),
+            parse_quote! {
+                fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+                    ::std::io::Read::read(&mut self.#field, buf)
+                }
+            },
+        ),
+        Forward::Write => (
+            parse_quote!(::std::io::Write),
+            parse_quote! {
+                fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+                    ::std::io::Write::write(&mut self.#field, buf)
+                }
+                fn flush(&mut self) -> ::std::io::Result<()> {
+                    ::std::io::Write::flush(&mut self.#field)
+                }
+            },
+        ),
+        Forward::Future => (
+            parse_quote!(::core::future::Future),
+            parse_quote! {
+                type Output = <#dst_field_ty as ::core::future::Future>::Output;
+                fn poll(
+                    self: ::core::pin::Pin<&mut Self>,
+                    cx: &mut ::core::task::Context<'_>,
+                ) -> ::core::task::Poll<Self::Output> {
+                    // SAFETY: the last field is structurally pinned, it is never moved out of `Self`
+                    let field = unsafe { ::core::pin::Pin::map_unchecked_mut(self, |this| &mut this.#field) };
+                    ::core::future::Future::poll(field, cx)
+                }
+            },
+        ),
+    };
+
+    let mut generics = dst.input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#dst_field_ty: #bound));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = dst.input.generics.split_for_impl();
+
+    parse_quote! {
+        impl #impl_generics #bound for #name #ty_generics #where_clause {
+            #body
+        }
+    }
+}
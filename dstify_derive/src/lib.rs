@@ -1,12 +1,29 @@
 //! proc macro crate for [dstify](https://github.com/jsen-/dstify)
 
+mod borrow_tail;
+mod buffer;
+mod c_declaration;
+mod cyclic;
+mod deref;
+mod endian;
+mod forward;
+mod make_mut;
+mod pod;
+mod replace_tail;
+mod resize_tail;
+mod sized_twin;
+mod tail_traits;
+mod thin_dst;
+mod upcast;
+
 use proc_macro2::TokenStream;
 use syn::{
-    Attribute, Data, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Ident, Type, TypeParamBound,
-    parse_macro_input, parse_quote, spanned::Spanned,
+    Attribute, Data, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Ident, LitStr, Member, Path,
+    Token, Type, TypeParamBound, TypeTraitObject, Visibility, parse_macro_input, parse_quote,
+    punctuated::Punctuated, spanned::Spanned,
 };
 
-#[proc_macro_derive(Dstify)]
+#[proc_macro_derive(Dstify, attributes(dstify))]
 pub fn dstify(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     match inner(input) {
@@ -18,6 +35,7 @@ pub fn dstify(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
 fn inner(input: DeriveInput) -> Result<TokenStream, TokenStream> {
     ensure_repr_c(&input, &input.attrs)?;
+    let options = Options::parse(&input.attrs)?;
 
     let a_struct = match &input.data {
         Data::Struct(a_struct) => a_struct,
@@ -37,9 +55,9 @@ fn inner(input: DeriveInput) -> Result<TokenStream, TokenStream> {
         }
     };
 
-    let (normal_fields, dst_field_name, dst_field_ty) = match &a_struct.fields {
-        Fields::Named(named) => derive_named(&input, named)?,
-        Fields::Unnamed(unnamed) => derive_unnamed(&input, unnamed)?,
+    let (header, tail) = match &a_struct.fields {
+        Fields::Named(named) => derive_named(&input, named, options.endian)?,
+        Fields::Unnamed(unnamed) => derive_unnamed(&input, unnamed, options.endian)?,
         Fields::Unit => {
             return Err(syn::Error::new(
                 a_struct.struct_token.span,
@@ -48,29 +66,163 @@ fn inner(input: DeriveInput) -> Result<TokenStream, TokenStream> {
             .into_compile_error());
         }
     };
+    let len_field = match &options.len_field {
+        Some(len_field) => {
+            if let Type::TraitObject(_) = tail.ty {
+                return Err(syn::Error::new(
+                    len_field.span(),
+                    "`#[dstify(len_field = ...)]` requires a `slice` DST last field",
+                )
+                .into_compile_error());
+            }
+            let index = len_field
+                .parse::<Member>()
+                .ok()
+                .and_then(|member| header.iter().position(|field| field.member == member));
+            let Some(index) = index else {
+                return Err(syn::Error::new(
+                    len_field.span(),
+                    "`len_field` must name a field before the last one",
+                )
+                .into_compile_error());
+            };
+            for (enabled, option) in [
+                (options.buffer.is_some(), "buffer"),
+                (options.resize_tail, "resize_tail"),
+                (options.cyclic, "cyclic"),
+            ] {
+                if enabled {
+                    return Err(syn::Error::new(
+                        len_field.span(),
+                        format!("`len_field` can't be combined with `{option}`"),
+                    )
+                    .into_compile_error());
+                }
+            }
+            Some(index)
+        }
+        None => None,
+    };
+    let dst = Dst {
+        input: &input,
+        header,
+        tail,
+        len_field,
+    };
 
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let dst_field_name = &dst.tail.ident;
+    let dst_field_ty = dst.tail.ty;
     let mut layouts = Vec::<TokenStream>::new();
     let mut inits = Vec::<TokenStream>::new();
-    let args = normal_fields
-        .map(|(ident, ty)| {
-            layouts.push(parse_quote!(::core::alloc::Layout::new::<#ty>()));
-            inits
-                .push(parse_quote!(::core::ptr::write(<*mut _>::cast(offsets.get_next()), #ident)));
-            parse_quote!(#ident: #ty)
-        })
+    for field in &dst.header {
+        let (ty, ident) = (field.ty, &field.ident);
+        let value = field.encode(parse_quote!(#ident));
+        layouts.push(parse_quote!(::core::alloc::Layout::new::<#ty>()));
+        inits.push(parse_quote!(::core::ptr::write(<*mut _>::cast(offsets.get_next()), #value)));
+    }
+    let args = dst
+        .args()
+        .map(|Field { ident, ty, .. }| parse_quote!(#ident: #ty))
         .collect::<Vec<TokenStream>>();
 
-    let res = match dst_field_ty {
+    let normal_tys = dst.header.iter().map(|field| field.ty);
+    let endians = dst.header.iter().map(|field| -> Type {
+        match field.endian {
+            None => parse_quote!(::dstify::private::NativeEndian),
+            Some(endian::Endian::Little) => parse_quote!(::dstify::private::LittleEndian),
+            Some(endian::Endian::Big) => parse_quote!(::dstify::private::BigEndian),
+        }
+    });
+    let mut extra = vec![parse_quote! {
+        impl #impl_generics ::dstify::private::DstFields for #name #ty_generics #where_clause {
+            type Normal = (#(#normal_tys,)*);
+            type Endian = (#(#endians,)*);
+            type Unsized = #dst_field_ty;
+        }
+    }];
+    if let (Type::TraitObject(_), Some(max_len)) = (dst_field_ty, &options.max_len) {
+        return Err(syn::Error::new(
+            max_len.value.span(),
+            "`#[dstify(max_len = ...)]` requires a `slice` DST last field",
+        )
+        .into_compile_error());
+    }
+    if let (Type::TraitObject(_), true) = (dst_field_ty, options.raw_parts) {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`#[dstify(raw_parts)]` requires a `slice` DST last field",
+        )
+        .into_compile_error());
+    }
+    extra.push(thin_dst::derive(&dst, options.max_len.as_ref()));
+    if dst.header.iter().any(|field| field.endian.is_some()) {
+        extra.push(endian::accessors(&dst));
+    }
+    if options.borrow_tail {
+        extra.push(borrow_tail::derive(&dst)?);
+    }
+    if options.deref {
+        extra.push(deref::derive(&dst));
+    }
+    if !options.forward.is_empty() {
+        extra.push(forward::derive(&dst, &options.forward));
+    }
+    if !options.tail_traits.is_empty() {
+        extra.push(tail_traits::derive(
+            &dst,
+            &options.tail_traits,
+            options.downcast,
+        )?);
+    }
+    if options.cyclic {
+        extra.push(cyclic::derive(&dst)?);
+    }
+    if options.with_tail {
+        extra.push(replace_tail::with_tail(&dst));
+    }
+    if options.c_declaration {
+        extra.push(c_declaration::derive(&dst)?);
+    }
+    if options.pod {
+        if options.cyclic {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`#[dstify(pod)]` can't be combined with `cyclic`, which can't zero the padding",
+            )
+            .into_compile_error());
+        }
+        extra.push(pod::derive(&dst, options.max_len.as_ref())?);
+    }
+    if options.make_mut {
+        extra.push(make_mut::derive(&dst)?);
+    }
+    if options.map_tail {
+        extra.push(replace_tail::map_tail(&dst));
+    }
+    if let Some(buffer_name) = &options.buffer {
+        extra.push(buffer::derive(&dst, buffer_name)?);
+    }
+    if options.resize_tail {
+        extra.push(resize_tail::derive(&dst, options.max_len.as_ref())?);
+    }
+    if !options.upcast_to.is_empty() {
+        extra.push(upcast::derive(&dst, &options.upcast_to));
+    }
+    if let Some(twin) = &options.sized_twin {
+        extra.push(sized_twin::derive(&dst, twin, options.downcast)?);
+    } else if options.downcast {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`#[dstify(downcast)]` requires `#[dstify(sized_twin = \"...\")]`",
+        )
+        .into_compile_error());
+    }
+
+    let res: TokenStream = match dst_field_ty {
         Type::TraitObject(trait_object) => {
-            let mut bounds = trait_object.bounds.clone();
-            if !bounds
-                .iter()
-                .any(|b| matches!(b, TypeParamBound::Lifetime(_)))
-            {
-                bounds.push(TypeParamBound::Lifetime(parse_quote!('static)));
-            }
+            let bounds = dyn_bounds(trait_object);
             parse_quote! {
                 impl #impl_generics #name #ty_generics #where_clause {
                     fn init_unsized<R, D>(#(#args,)* #dst_field_name: D) -> R
@@ -79,10 +231,9 @@ fn inner(input: DeriveInput) -> Result<TokenStream, TokenStream> {
                         D: #bounds,
                     {
                         unsafe {
-                            let fat_ptr = ::core::result::Result::unwrap(::dstify::private::alloc_dyn::<Self, R, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
+                            ::core::result::Result::unwrap(::dstify::private::alloc_dyn::<Self, R, _, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
                                 #(#inits;)*
-                            }));
-                            R::cast(fat_ptr as *mut D as *mut (dyn #bounds) as *mut Self)
+                            }, |fat_ptr| fat_ptr as *mut D as *mut (dyn #bounds) as *mut Self))
                         }
                     }
                     fn init_unsized_checked<R, D>(#(#args,)* #dst_field_name: D) -> ::core::result::Result<R, ::core::alloc::LayoutError>
@@ -91,56 +242,299 @@ fn inner(input: DeriveInput) -> Result<TokenStream, TokenStream> {
                         D: #bounds,
                     {
                         unsafe {
-                            let fat_ptr = ::dstify::private::alloc_dyn::<Self, R, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
+                            ::dstify::private::alloc_dyn::<Self, R, _, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
                                 #(#inits;)*
-                            })?;
-                            Ok(R::cast(fat_ptr as *mut D as *mut (dyn #bounds) as *mut Self))
+                            }, |fat_ptr| fat_ptr as *mut D as *mut (dyn #bounds) as *mut Self)
                         }
                     }
                 }
             }
         }
         _ => {
+            let (check_len, check_len_checked, error): (TokenStream, TokenStream, Type) =
+                match &options.max_len {
+                    Some(max_len) => {
+                        let value = &max_len.value;
+                        (
+                            parse_quote! {
+                                if let ::core::result::Result::Err(err) = ::dstify::private::check_len(#dst_field_name, #value) {
+                                    ::core::panic!("{}", err);
+                                }
+                            },
+                            parse_quote!(::dstify::private::check_len(#dst_field_name, #value)?;),
+                            parse_quote!(::dstify::InitError),
+                        )
+                    }
+                    None => (
+                        TokenStream::new(),
+                        TokenStream::new(),
+                        parse_quote!(::core::alloc::LayoutError),
+                    ),
+                };
+            let (check_len, check_len_checked, error, from_c_ptr) = match dst.len_field() {
+                Some(
+                    len_field @ Field {
+                        ident, ty, member, ..
+                    },
+                ) => {
+                    let read_len =
+                        len_field.decode(parse_quote!((&raw const (*header).#member).read()));
+                    let len_field_name = match member {
+                        Member::Named(ident) => ident.to_string(),
+                        Member::Unnamed(index) => index.index.to_string(),
+                    };
+                    (
+                        parse_quote! {
+                            #check_len
+                            let #ident: #ty = match ::dstify::private::len_field(#dst_field_name, #len_field_name) {
+                                ::core::result::Result::Ok(len) => len,
+                                ::core::result::Result::Err(err) => ::core::panic!("{}", err),
+                            };
+                        },
+                        parse_quote! {
+                            #check_len_checked
+                            let #ident: #ty = ::dstify::private::len_field(#dst_field_name, #len_field_name)?;
+                        },
+                        parse_quote!(::dstify::InitError),
+                        parse_quote! {
+                            /// # Safety
+                            /// `ptr` must point to an initialized instance of `Self`, whose last field has as many elements
+                            /// as its length field says, valid for reads for the lifetime `'a`.
+                            unsafe fn from_c_ptr<'a>(ptr: *const u8) -> &'a Self {
+                                unsafe {
+                                    // the length field is within the header, so an empty last field is enough to reach it
+                                    let header = ::core::ptr::slice_from_raw_parts(ptr, 0) as *const Self;
+                                    let len = ::dstify::private::LenField::into_len(#read_len);
+                                    // this cast must remain here, cannot be done using generics
+                                    &*(::core::ptr::slice_from_raw_parts(ptr, len) as *const Self)
+                                }
+                            }
+                        },
+                    )
+                }
+                None => (check_len, check_len_checked, error, TokenStream::new()),
+            };
+            let raw_parts: TokenStream = if options.raw_parts {
+                parse_quote! {
+                    fn into_raw_parts(this: ::dstify::private::Box<Self>) -> (::core::ptr::NonNull<u8>, usize) {
+                        let ptr = ::dstify::private::Box::into_raw(this);
+                        // SAFETY: the pointer from `Box` is not null
+                        (unsafe { ::core::ptr::NonNull::new_unchecked(ptr.cast::<u8>()) }, (ptr as *const [()]).len())
+                    }
+                    /// # Safety
+                    /// `ptr` and `len` must come from `into_raw_parts`.
+                    unsafe fn from_raw_parts(ptr: ::core::ptr::NonNull<u8>, len: usize) -> ::dstify::private::Box<Self> {
+                        // this cast must remain here, cannot be done using generics
+                        unsafe { ::dstify::private::Box::from_raw(::core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len) as *mut Self) }
+                    }
+                }
+            } else {
+                TokenStream::new()
+            };
             parse_quote! {
                 impl #impl_generics #name #ty_generics #where_clause {
                     fn init_unsized<R>(#(#args,)* #dst_field_name: &#dst_field_ty) -> R
                     where
                         R: ::dstify::SmartPointer<Self>
                     {
+                        #check_len
                         unsafe {
-                            let fat_ptr = ::core::result::Result::unwrap(::dstify::private::alloc_slice::<Self, R, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
+                            ::core::result::Result::unwrap(::dstify::private::alloc_slice::<Self, R, _, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
                                 #(#inits;)*
-                            }));
+                            },
                             // this cast must remain here, cannot be done using generics
-                            R::cast(fat_ptr as *mut Self)
+                            |fat_ptr| fat_ptr as *mut Self))
                         }
                     }
-                    fn init_unsized_checked<R>(#(#args,)* #dst_field_name: &#dst_field_ty) -> ::core::result::Result<R, ::core::alloc::LayoutError>
+                    fn init_unsized_checked<R>(#(#args,)* #dst_field_name: &#dst_field_ty) -> ::core::result::Result<R, #error>
                     where
                         R: ::dstify::SmartPointer<Self>
                     {
+                        #check_len_checked
                         unsafe {
-                            let fat_ptr = ::dstify::private::alloc_slice::<Self, R, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
+                            ::core::result::Result::map_err(::dstify::private::alloc_slice::<Self, R, _, _, _, _>([#(#layouts),*], #dst_field_name, |offsets| {
                                 #(#inits;)*
-                            })?;
+                            },
                             // this cast must remain here, cannot be done using generics
-                            Ok(R::cast(fat_ptr as *mut Self))
+                            |fat_ptr| fat_ptr as *mut Self), <#error>::from)
                         }
                     }
+                    #raw_parts
+                    #from_c_ptr
                 }
             }
         }
     };
 
-    Ok(res)
+    Ok(parse_quote! {
+        #res
+        #(#extra)*
+    })
+}
+
+/// Bounds of the `dyn Trait` last field, `'static` unless a lifetime is given explicitly.
+fn dyn_bounds(trait_object: &TypeTraitObject) -> Punctuated<TypeParamBound, Token![+]> {
+    let mut bounds = trait_object.bounds.clone();
+    if !bounds
+        .iter()
+        .any(|b| matches!(b, TypeParamBound::Lifetime(_)))
+    {
+        bounds.push(TypeParamBound::Lifetime(parse_quote!('static)));
+    }
+    bounds
+}
+
+/// The struct `Dstify` is being derived for, split into its sized fields and the DST last field.
+struct Dst<'a> {
+    input: &'a DeriveInput,
+    header: Vec<Field<'a>>,
+    tail: Field<'a>,
+    /// index of the `#[dstify(len_field = ...)]` in `header`
+    len_field: Option<usize>,
 }
 
-type FieldIter<'a> = Box<dyn Iterator<Item = (Ident, &'a Type)> + 'a>;
+impl<'a> Dst<'a> {
+    /// The header field holding the length of the last field, filled in by the constructors.
+    fn len_field(&self) -> Option<&Field<'a>> {
+        self.len_field.map(|index| &self.header[index])
+    }
+
+    /// The header fields taken by `init_unsized`, all except the `len_field`.
+    fn args(&self) -> impl Iterator<Item = &Field<'a>> {
+        self.header
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != self.len_field)
+            .map(|(_, field)| field)
+    }
+}
+
+struct Field<'a> {
+    /// `name` for named structs, `N` for tuple structs
+    member: Member,
+    /// name of the corresponding `init_unsized` argument
+    ident: Ident,
+    ty: &'a Type,
+    vis: &'a Visibility,
+    /// byte order the field is stored in, see `#[dstify(endian = "...")]`
+    endian: Option<endian::Endian>,
+}
+
+#[derive(Default)]
+struct Options {
+    borrow_tail: bool,
+    deref: bool,
+    forward: Vec<forward::Forward>,
+    tail_traits: Vec<Path>,
+    sized_twin: Option<Ident>,
+    downcast: bool,
+    upcast_to: Vec<Type>,
+    with_tail: bool,
+    map_tail: bool,
+    resize_tail: bool,
+    buffer: Option<Ident>,
+    max_len: Option<thin_dst::MaxLen>,
+    cyclic: bool,
+    make_mut: bool,
+    len_field: Option<LitStr>,
+    c_declaration: bool,
+    raw_parts: bool,
+    pod: bool,
+    endian: Option<endian::Endian>,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> Result<Self, TokenStream> {
+        let mut options = Options::default();
+        for attr in attrs {
+            if !attr.path().is_ident("dstify") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("borrow_tail") {
+                    options.borrow_tail = true;
+                    Ok(())
+                } else if meta.path.is_ident("deref") {
+                    options.deref = true;
+                    Ok(())
+                } else if meta.path.is_ident("forward") {
+                    meta.parse_nested_meta(|meta| {
+                        options.forward.push(forward::Forward::parse(meta)?);
+                        Ok(())
+                    })
+                } else if meta.path.is_ident("tail_traits") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    options.tail_traits.extend(
+                        content.parse_terminated(<Path as syn::parse::Parse>::parse, Token![,])?,
+                    );
+                    Ok(())
+                } else if meta.path.is_ident("sized_twin") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.sized_twin = Some(name.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("downcast") {
+                    options.downcast = true;
+                    Ok(())
+                } else if meta.path.is_ident("with_tail") {
+                    options.with_tail = true;
+                    Ok(())
+                } else if meta.path.is_ident("map_tail") {
+                    options.map_tail = true;
+                    Ok(())
+                } else if meta.path.is_ident("resize_tail") {
+                    options.resize_tail = true;
+                    Ok(())
+                } else if meta.path.is_ident("buffer") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.buffer = Some(name.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("make_mut") {
+                    options.make_mut = true;
+                    Ok(())
+                } else if meta.path.is_ident("cyclic") {
+                    options.cyclic = true;
+                    Ok(())
+                } else if meta.path.is_ident("endian") {
+                    options.endian = Some(endian::Endian::parse(meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("pod") {
+                    options.pod = true;
+                    Ok(())
+                } else if meta.path.is_ident("c_declaration") {
+                    options.c_declaration = true;
+                    Ok(())
+                } else if meta.path.is_ident("raw_parts") {
+                    options.raw_parts = true;
+                    Ok(())
+                } else if meta.path.is_ident("len_field") {
+                    options.len_field = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("max_len") {
+                    options.max_len = Some(thin_dst::MaxLen::parse(meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("upcast_to") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    options.upcast_to.extend(
+                        content.parse_terminated(<Type as syn::parse::Parse>::parse, Token![,])?,
+                    );
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `dstify` attribute"))
+                }
+            })
+            .map_err(syn::Error::into_compile_error)?;
+        }
+        Ok(options)
+    }
+}
 
 fn derive_named<'a>(
     input: &'a DeriveInput,
     fields: &'a FieldsNamed,
-) -> Result<(FieldIter<'a>, Ident, &'a Type), TokenStream> {
+    endian: Option<endian::Endian>,
+) -> Result<(Vec<Field<'a>>, Field<'a>), TokenStream> {
     let mut fields = fields.named.iter().rev();
     let Some(last_field) = fields.next() else {
         return Err(syn::Error::new(
@@ -155,31 +549,47 @@ fn derive_named<'a>(
         .as_ref()
         .expect("bug: named struct field missing ident")
         .clone();
-    let dst_field_ty = &last_field.ty;
-
-    let normal_fields = fields.rev().map(|field| {
-        let ident = field
-            .ident
-            .as_ref()
-            .expect("bug: named struct field missing ident");
-        let mut name = ident.to_string();
-        let name = match name.as_bytes() {
-            [b'o', b'f', b'f', b's', b'e', b't', b's', ..] => {
-                name.push('_');
-                Ident::new(&name, ident.span())
-            }
-            _ => ident.clone(),
-        };
-        let ty = &field.ty;
-        (name, ty)
-    });
-    Ok((Box::new(normal_fields), dst_field_ident, dst_field_ty))
+    ensure_no_endian(last_field)?;
+    let tail = Field {
+        member: Member::Named(dst_field_ident.clone()),
+        ident: dst_field_ident,
+        ty: &last_field.ty,
+        vis: &last_field.vis,
+        endian: None,
+    };
+
+    let header = fields
+        .rev()
+        .map(|field| {
+            let ident = field
+                .ident
+                .as_ref()
+                .expect("bug: named struct field missing ident");
+            let mut name = ident.to_string();
+            let name = match name.as_bytes() {
+                [b'o', b'f', b'f', b's', b'e', b't', b's', ..] => {
+                    name.push('_');
+                    Ident::new(&name, ident.span())
+                }
+                _ => ident.clone(),
+            };
+            Ok(Field {
+                member: Member::Named(ident.clone()),
+                ident: name,
+                ty: &field.ty,
+                vis: &field.vis,
+                endian: endian::Endian::of_field(&field.attrs, &field.ty, endian)?,
+            })
+        })
+        .collect::<Result<_, TokenStream>>()?;
+    Ok((header, tail))
 }
 
 fn derive_unnamed<'a>(
     input: &'a DeriveInput,
     fields: &'a FieldsUnnamed,
-) -> Result<(FieldIter<'a>, Ident, &'a Type), TokenStream> {
+    endian: Option<endian::Endian>,
+) -> Result<(Vec<Field<'a>>, Field<'a>), TokenStream> {
     let mut it = fields.unnamed.iter().enumerate().rev();
     let Some(last_field) = it.next() else {
         return Err(syn::Error::new(
@@ -189,15 +599,42 @@ fn derive_unnamed<'a>(
         .into_compile_error());
     };
 
-    let dst_field_ident = Ident::new(&format!("f{}", last_field.0), last_field.1.span());
-    let dst_field_ty = &last_field.1.ty;
+    let unnamed_field = |(index, field): (usize, &'a syn::Field), endian| Field {
+        member: Member::Unnamed(syn::Index {
+            index: index as u32,
+            span: field.span(),
+        }),
+        ident: Ident::new(&format!("f{index}"), field.span()),
+        ty: &field.ty,
+        vis: &field.vis,
+        endian,
+    };
+    ensure_no_endian(last_field.1)?;
+    let tail = unnamed_field(last_field, None);
+    let header = it
+        .rev()
+        .map(|(index, field)| {
+            let endian = endian::Endian::of_field(&field.attrs, &field.ty, endian)?;
+            Ok(unnamed_field((index, field), endian))
+        })
+        .collect::<Result<_, TokenStream>>()?;
+    Ok((header, tail))
+}
 
-    let normal_fields = it.rev().map(|field| {
-        let name = Ident::new(&format!("f{}", field.0), field.1.span());
-        let ty = &field.1.ty;
-        (name, ty)
-    });
-    Ok((Box::new(normal_fields), dst_field_ident, dst_field_ty))
+/// The last field can't have a byte order, as it isn't an integer.
+fn ensure_no_endian(field: &syn::Field) -> Result<(), TokenStream> {
+    match field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("dstify"))
+    {
+        Some(attr) => Err(syn::Error::new(
+            attr.span(),
+            "`#[dstify(...)]` is only supported on the fields before the last one",
+        )
+        .into_compile_error()),
+        None => Ok(()),
+    }
 }
 
 fn ensure_repr_c(input: &DeriveInput, attrs: &[Attribute]) -> Result<(), TokenStream> {
@@ -0,0 +1,41 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote, spanned::Spanned};
+
+/// `make_mut`, cloning the value of a shared pointer into a fresh allocation before handing out a mutable reference.
+pub fn derive(dst: &Dst) -> Result<TokenStream, TokenStream> {
+    if let Type::TraitObject(_) = dst.tail.ty {
+        return Err(syn::Error::new(
+            dst.tail.ty.span(),
+            "`#[dstify(make_mut)]` requires a `slice` DST last field",
+        )
+        .into_compile_error());
+    }
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let clones = dst.args().map(|field| -> TokenStream {
+        let member = &field.member;
+        field.decode(parse_quote!(::core::clone::Clone::clone(&value.#member)))
+    });
+    let clone_bounds = dst.args().map(|field| -> TokenStream {
+        let ty = field.ty;
+        parse_quote!(#ty: ::core::clone::Clone)
+    });
+
+    Ok(parse_quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn make_mut<R>(this: &mut R) -> &mut Self
+            where
+                R: ::dstify::MakeMut<Self>,
+                #(#clone_bounds,)*
+            {
+                if R::get_mut(this).is_none() {
+                    let value: &Self = this;
+                    *this = Self::init_unsized(#(#clones,)* &value.#field);
+                }
+                R::get_mut(this).expect("a fresh allocation is unique")
+            }
+        }
+    })
+}
@@ -0,0 +1,128 @@
+use crate::{Dst, thin_dst::MaxLen};
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote, spanned::Spanned};
+
+/// `ref_from_bytes`, `mut_from_bytes`, `from_bytes` and `as_bytes`, converting between bytes
+/// and a struct whose fields are all plain old data.
+pub fn derive(dst: &Dst, max_len: Option<&MaxLen>) -> Result<TokenStream, TokenStream> {
+    let Type::Slice(slice) = dst.tail.ty else {
+        return Err(syn::Error::new(
+            dst.tail.ty.span(),
+            "`#[dstify(pod)]` requires a `[T]` last field",
+        )
+        .into_compile_error());
+    };
+    let elem = &slice.elem;
+    let input = dst.input;
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in &dst.header {
+        let ty = field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::dstify::Pod));
+    }
+    where_clause
+        .predicates
+        .push(parse_quote!(#elem: ::dstify::Pod));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let layouts = dst
+        .header
+        .iter()
+        .map(|field| -> TokenStream {
+            let ty = field.ty;
+            parse_quote!(::core::alloc::Layout::new::<#ty>())
+        })
+        .collect::<Vec<_>>();
+    let header_ty = dst.header.iter().map(|field| field.ty);
+    let tail = &dst.tail.member;
+    let elem_layout: TokenStream = parse_quote!(::core::alloc::Layout::new::<#elem>());
+    let max_len: TokenStream = match max_len {
+        Some(MaxLen { value, .. }) => parse_quote!(#value),
+        None => parse_quote!(::core::primitive::usize::MAX),
+    };
+    let assert_layout: TokenStream =
+        parse_quote!(::dstify::private::assert_pod_layout([#(#layouts),*], #elem_layout));
+    // generic structs are checked when the methods are instantiated
+    let (check, method_check): (TokenStream, TokenStream) = if input.generics.params.is_empty() {
+        (
+            parse_quote!(const _: () = #assert_layout;),
+            TokenStream::new(),
+        )
+    } else {
+        (TokenStream::new(), parse_quote!(const { #assert_layout };))
+    };
+
+    Ok(parse_quote! {
+        #check
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn ref_from_bytes(bytes: &[u8]) -> ::core::result::Result<&Self, ::dstify::FromBytesError> {
+                #method_check
+                let len = ::dstify::private::pod_len([#(#layouts),*], #elem_layout, bytes, ::core::option::Option::None, #max_len)?;
+                // SAFETY: the bytes are aligned and exactly as long as `Self`, all of whose fields are `Pod`
+                // this cast must remain here, cannot be done using generics
+                ::core::result::Result::Ok(unsafe { &*(::core::ptr::slice_from_raw_parts(bytes.as_ptr(), len) as *const Self) })
+            }
+            fn mut_from_bytes(bytes: &mut [u8]) -> ::core::result::Result<&mut Self, ::dstify::FromBytesError> {
+                #method_check
+                let len = ::dstify::private::pod_len([#(#layouts),*], #elem_layout, bytes, ::core::option::Option::None, #max_len)?;
+                // SAFETY: the bytes are aligned and exactly as long as `Self`, all of whose fields are `Pod`
+                // this cast must remain here, cannot be done using generics
+                ::core::result::Result::Ok(unsafe { &mut *(::core::ptr::slice_from_raw_parts_mut(bytes.as_mut_ptr(), len) as *mut Self) })
+            }
+            fn from_bytes<R>(bytes: &[u8]) -> ::core::result::Result<R, ::dstify::FromBytesError>
+            where
+                R: ::dstify::SmartPointer<Self>,
+            {
+                #method_check
+                unsafe {
+                    ::dstify::private::pod_from_bytes::<Self, R, _, _>([#(#layouts),*], #elem_layout, bytes, ::core::option::Option::None, #max_len,
+                        // this cast must remain here, cannot be done using generics
+                        |fat_ptr| fat_ptr as *mut Self)
+                }
+            }
+            fn as_bytes(&self) -> &[u8] {
+                // SAFETY: the fields are `Pod` without padding in between, and the constructors zero the trailing padding
+                unsafe { ::core::slice::from_raw_parts((self as *const Self).cast::<u8>(), ::core::mem::size_of_val(self)) }
+            }
+        }
+
+        unsafe impl #impl_generics ::dstify::PodDst for #name #ty_generics #where_clause {
+            const ALIGN: usize = ::dstify::private::max_align([#(::core::mem::align_of::<#header_ty>(),)* ::core::mem::align_of::<#elem>()]);
+
+            #[inline]
+            fn as_bytes(&self) -> &[u8] {
+                Self::as_bytes(self)
+            }
+            #[inline]
+            fn ref_from_bytes(bytes: &[u8]) -> ::core::result::Result<&Self, ::dstify::FromBytesError> {
+                Self::ref_from_bytes(bytes)
+            }
+            #[inline]
+            fn from_bytes<R: ::dstify::SmartPointer<Self>>(bytes: &[u8]) -> ::core::result::Result<R, ::dstify::FromBytesError> {
+                Self::from_bytes(bytes)
+            }
+            #[inline]
+            fn tail_len(&self) -> usize {
+                self.#tail.len()
+            }
+            fn ref_from_bytes_with_len(bytes: &[u8], len: usize) -> ::core::result::Result<&Self, ::dstify::FromBytesError> {
+                #method_check
+                let len = ::dstify::private::pod_len([#(#layouts),*], #elem_layout, bytes, ::core::option::Option::Some(len), #max_len)?;
+                // SAFETY: the bytes are aligned and exactly as long as `Self`, all of whose fields are `Pod`
+                // this cast must remain here, cannot be done using generics
+                ::core::result::Result::Ok(unsafe { &*(::core::ptr::slice_from_raw_parts(bytes.as_ptr(), len) as *const Self) })
+            }
+            fn from_bytes_with_len<R: ::dstify::SmartPointer<Self>>(bytes: &[u8], len: usize) -> ::core::result::Result<R, ::dstify::FromBytesError> {
+                #method_check
+                unsafe {
+                    ::dstify::private::pod_from_bytes::<Self, R, _, _>([#(#layouts),*], #elem_layout, bytes, ::core::option::Option::Some(len), #max_len,
+                        // this cast must remain here, cannot be done using generics
+                        |fat_ptr| fat_ptr as *mut Self)
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,108 @@
+use crate::{Dst, dyn_bounds};
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote};
+
+/// `with_tail`, constructing a new instance from clones of the normal fields and a new last field.
+pub fn with_tail(dst: &Dst) -> TokenStream {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let dst_field_name = &dst.tail.ident;
+    let dst_field_ty = dst.tail.ty;
+    let clones = dst.args().map(|field| -> TokenStream {
+        let member = &field.member;
+        field.decode(parse_quote!(::core::clone::Clone::clone(&self.#member)))
+    });
+    let clone_bounds = dst.args().map(|field| -> TokenStream {
+        let ty = field.ty;
+        parse_quote!(#ty: ::core::clone::Clone)
+    });
+
+    match dst_field_ty {
+        Type::TraitObject(trait_object) => {
+            let bounds = dyn_bounds(trait_object);
+            parse_quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    fn with_tail<R, D>(&self, #dst_field_name: D) -> R
+                    where
+                        R: ::dstify::SmartPointer<Self>,
+                        D: #bounds,
+                        #(#clone_bounds,)*
+                    {
+                        Self::init_unsized(#(#clones,)* #dst_field_name)
+                    }
+                }
+            }
+        }
+        _ => parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                fn with_tail<R>(&self, #dst_field_name: &#dst_field_ty) -> R
+                where
+                    R: ::dstify::SmartPointer<Self>,
+                    #(#clone_bounds,)*
+                {
+                    Self::init_unsized(#(#clones,)* #dst_field_name)
+                }
+            }
+        },
+    }
+}
+
+/// `map_tail`, moving the normal fields out of a `Box` into a new instance with the last field returned by `f`.
+pub fn map_tail(dst: &Dst) -> TokenStream {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let dst_field_ty = dst.tail.ty;
+    let idents = dst.args().map(|field| &field.ident).collect::<Vec<_>>();
+    let values = dst.args().map(|field| -> TokenStream {
+        let ident = &field.ident;
+        field.decode(parse_quote!(#ident))
+    });
+    let reads = dst.args().map(|field| -> TokenStream {
+        let ident = &field.ident;
+        let member = &field.member;
+        parse_quote!(let #ident = ::core::ptr::read(&raw const (*this).#member);)
+    });
+
+    let (generics, bounds, new_tail): (TokenStream, TokenStream, TokenStream) = match dst_field_ty {
+        Type::TraitObject(trait_object) => {
+            let bounds = dyn_bounds(trait_object);
+            (
+                parse_quote!(<R, F, D>),
+                parse_quote!(F: ::core::ops::FnOnce(&#dst_field_ty) -> D, D: #bounds,),
+                parse_quote!(new_tail),
+            )
+        }
+        _ => (
+            parse_quote!(<R, F, B>),
+            parse_quote! {
+                F: ::core::ops::FnOnce(&#dst_field_ty) -> B,
+                B: ::core::borrow::Borrow<#dst_field_ty>,
+            },
+            parse_quote!(::core::borrow::Borrow::borrow(&new_tail)),
+        ),
+    };
+
+    parse_quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn map_tail #generics(this: ::dstify::private::Box<Self>, f: F) -> R
+            where
+                R: ::dstify::SmartPointer<Self>,
+                #bounds
+            {
+                let new_tail = f(&this.#field);
+                let layout = ::core::alloc::Layout::for_value::<Self>(&this);
+                let this = ::dstify::private::Box::into_raw(this);
+                // SAFETY: the normal fields are moved out and the last one is dropped exactly once,
+                // then the allocation is freed without dropping `Self`
+                let (#(#idents,)*) = unsafe {
+                    #(#reads)*
+                    ::core::ptr::drop_in_place(&raw mut (*this).#field);
+                    ::dstify::private::dealloc(this.cast::<u8>(), layout);
+                    (#(#idents,)*)
+                };
+                Self::init_unsized(#(#values,)* #new_tail)
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Dst, thin_dst::MaxLen};
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote, spanned::Spanned};
+
+/// `extend_tail`, `truncate_tail` and `resize_tail_with` reallocating a `Box` with `[T]` last field in place.
+///
+/// With `max_len`, growing returns `InitError::TooLong` before reallocating if the last field would exceed it.
+pub fn derive(dst: &Dst, max_len: Option<&MaxLen>) -> Result<TokenStream, TokenStream> {
+    let Type::Slice(slice) = dst.tail.ty else {
+        return Err(syn::Error::new(
+            dst.tail.ty.span(),
+            "`#[dstify(resize_tail)]` requires a `[T]` last field",
+        )
+        .into_compile_error());
+    };
+    let elem = &slice.elem;
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let layouts = dst.header.iter().map(|field| -> TokenStream {
+        let ty = field.ty;
+        parse_quote!(::core::alloc::Layout::new::<#ty>())
+    });
+    let resize: TokenStream = parse_quote! {
+        unsafe {
+            let len = this.#field.len();
+            ::dstify::private::Resize::<Self, #elem, _>::new(this, [#(#layouts),*], len, |base, len| {
+                // this cast must remain here, cannot be done using generics
+                ::core::ptr::slice_from_raw_parts_mut(base, len) as *mut Self
+            })
+        }
+    };
+
+    let (ret, check_extend, check_resize, ok): (
+        TokenStream,
+        TokenStream,
+        TokenStream,
+        TokenStream,
+    ) = match max_len {
+        Some(MaxLen { value, .. }) => (
+            parse_quote!(-> ::core::result::Result<(), ::dstify::InitError>),
+            parse_quote!(::dstify::private::check_new_len(this.#field.len().saturating_add(more.len()), #value)?;),
+            parse_quote!(::dstify::private::check_new_len(new_len, #value)?;),
+            parse_quote!(::core::result::Result::Ok(())),
+        ),
+        None => Default::default(),
+    };
+
+    Ok(parse_quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn extend_tail(this: &mut ::dstify::private::Box<Self>, more: &[#elem]) #ret
+            where
+                #elem: ::core::clone::Clone,
+            {
+                #check_extend
+                let mut more = more.iter();
+                #resize.extend_with(more.len(), || ::core::clone::Clone::clone(more.next().unwrap()));
+                #ok
+            }
+            fn truncate_tail(this: &mut ::dstify::private::Box<Self>, len: usize) {
+                #resize.truncate(len)
+            }
+            fn resize_tail_with<F>(this: &mut ::dstify::private::Box<Self>, new_len: usize, f: F) #ret
+            where
+                F: ::core::ops::FnMut() -> #elem,
+            {
+                #check_resize
+                let additional = new_len.checked_sub(this.#field.len());
+                let resize = #resize;
+                match additional {
+                    ::core::option::Option::Some(additional) => resize.extend_with(additional, f),
+                    ::core::option::Option::None => resize.truncate(new_len),
+                }
+                #ok
+            }
+        }
+    })
+}
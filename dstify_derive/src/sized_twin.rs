@@ -0,0 +1,216 @@
+use crate::{Dst, dyn_bounds};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{GenericParam, Generics, Ident, Member, Type, parse_quote};
+
+/// Generic struct with the same fields as the DST, except for the last one, which becomes `T`.
+/// Being `#[repr(C)]` too, it has the same layout as the DST whose last field holds a `T`,
+/// so boxed twins are converted to the DST in place.
+pub fn derive(dst: &Dst, twin: &Ident, downcast: bool) -> Result<TokenStream, TokenStream> {
+    let input = dst.input;
+    if input.generics.type_params().any(|param| param.ident == "T") {
+        return Err(syn::Error::new(
+            twin.span(),
+            "`#[dstify(sized_twin = \"...\")]` uses `T` as the type of the last field, rename the struct's type parameter `T`",
+        )
+        .into_compile_error());
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let mut twin_generics = input.generics.clone();
+    twin_generics.params.push(parse_quote!(T));
+    let twin_where_clause = &twin_generics.where_clause;
+    let doc = format!("Sized counterpart of [`{name}`] with the last field of type `T`.");
+
+    let header_vis = dst.header.iter().map(|field| field.vis);
+    let header_ty = dst.header.iter().map(|field| field.ty);
+    let tail_vis = dst.tail.vis;
+    let tail_member = &dst.tail.member;
+    // parameters used only by the last field would be unused by the twin
+    let marker = (!uses_all(&input.generics, dst.header.iter().map(|field| field.ty)))
+        .then(|| marker(&input.generics));
+    let definition: TokenStream = match &dst.tail.member {
+        Member::Named(tail_ident) => {
+            let header_ident = dst.header.iter().map(|field| &field.member);
+            let marker = marker.iter();
+            parse_quote! {
+                #[doc = #doc]
+                #[repr(C)]
+                #vis struct #twin #twin_generics #twin_where_clause {
+                    #(#header_vis #header_ident: #header_ty,)*
+                    #tail_vis #tail_ident: T,
+                    #(#vis _marker: #marker,)*
+                }
+            }
+        }
+        Member::Unnamed(_) => {
+            let marker = marker.iter();
+            parse_quote! {
+                #[doc = #doc]
+                #[repr(C)]
+                #vis struct #twin #twin_generics (#(#header_vis #header_ty,)* #tail_vis T, #(#vis #marker,)*) #twin_where_clause;
+            }
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let conversions: TokenStream = match dst.tail.ty {
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            let mut from_generics = input.generics.clone();
+            from_generics.params.push(parse_quote!(const N: usize));
+            let (from_impl_generics, _, _) = from_generics.split_for_impl();
+            let twin_ty = twin_with(twin, &input.generics, parse_quote!([#elem; N]));
+            parse_quote! {
+                impl #from_impl_generics ::core::convert::From<::dstify::private::Box<#twin_ty>> for ::dstify::private::Box<#name #ty_generics> #where_clause {
+                    fn from(twin: ::dstify::private::Box<#twin_ty>) -> Self {
+                        let base = ::dstify::private::Box::into_raw(twin).cast::<#elem>();
+                        // SAFETY: the twin with `[T; N]` has the same layout as the DST with `N` elements
+                        unsafe {
+                            let this = ::core::ptr::slice_from_raw_parts_mut(base, N) as *mut #name #ty_generics;
+                            let end = (&raw const (*this).#tail_member).byte_offset_from(this) as usize + ::core::mem::size_of::<[#elem; N]>();
+                            ::dstify::private::zero_trailing(base.cast::<u8>(), end, ::core::mem::size_of::<#twin_ty>());
+                            ::dstify::private::Box::from_raw(this)
+                        }
+                    }
+                }
+            }
+        }
+        Type::TraitObject(trait_object) => {
+            let bounds = dyn_bounds(trait_object);
+            let mut from_generics = input.generics.clone();
+            from_generics.params.push(parse_quote!(D: #bounds));
+            let (from_impl_generics, _, _) = from_generics.split_for_impl();
+            let twin_ty = twin_with(twin, &input.generics, parse_quote!(D));
+            parse_quote! {
+                impl #from_impl_generics ::core::convert::From<::dstify::private::Box<#twin_ty>> for ::dstify::private::Box<#name #ty_generics> #where_clause {
+                    fn from(twin: ::dstify::private::Box<#twin_ty>) -> Self {
+                        let base = ::dstify::private::Box::into_raw(twin).cast::<D>();
+                        // SAFETY: the twin with `D` has the same layout as the DST holding a `D`
+                        unsafe { ::dstify::private::Box::from_raw(base as *mut (dyn #bounds) as *mut #name #ty_generics) }
+                    }
+                }
+            }
+        }
+        // `str`, `CStr`, ... have no sized counterpart
+        _ => TokenStream::new(),
+    };
+
+    let downcasts = if downcast {
+        let Type::TraitObject(_) = dst.tail.ty else {
+            return Err(syn::Error::new(
+                twin.span(),
+                "`#[dstify(downcast)]` requires a `dyn Trait` DST last field with `Any` supertrait",
+            )
+            .into_compile_error());
+        };
+        let field = &dst.tail.member;
+        let twin_ty = twin_with(twin, &input.generics, parse_quote!(T));
+        parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                fn downcast<T: ::core::any::Any>(
+                    self: ::dstify::private::Box<Self>,
+                ) -> ::core::result::Result<::dstify::private::Box<#twin_ty>, ::dstify::private::Box<Self>> {
+                    if <dyn ::core::any::Any>::is::<T>(&self.#field) {
+                        // SAFETY: the last field is a `T`, so the allocation has the layout of the sized twin
+                        let ptr = ::dstify::private::Box::into_raw(self).cast::<#twin_ty>();
+                        ::core::result::Result::Ok(unsafe { ::dstify::private::Box::from_raw(ptr) })
+                    } else {
+                        ::core::result::Result::Err(self)
+                    }
+                }
+                fn downcast_ref<T: ::core::any::Any>(&self) -> ::core::option::Option<&#twin_ty> {
+                    if <dyn ::core::any::Any>::is::<T>(&self.#field) {
+                        // SAFETY: the last field is a `T`, so `self` has the layout of the sized twin
+                        ::core::option::Option::Some(unsafe { &*(self as *const Self).cast::<#twin_ty>() })
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+                fn downcast_mut<T: ::core::any::Any>(&mut self) -> ::core::option::Option<&mut #twin_ty> {
+                    if <dyn ::core::any::Any>::is::<T>(&self.#field) {
+                        // SAFETY: the last field is a `T`, so `self` has the layout of the sized twin
+                        ::core::option::Option::Some(unsafe { &mut *(self as *mut Self).cast::<#twin_ty>() })
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    Ok(parse_quote! {
+        #definition
+        #conversions
+        #downcasts
+    })
+}
+
+/// `PhantomData<fn() -> (&'a (), A)>` for struct generics `<'a, A>`, using all of them without affecting the layout,
+/// auto traits or drop check of a twin.
+pub fn marker(generics: &Generics) -> Type {
+    let params = generics
+        .params
+        .iter()
+        .filter_map(|param| -> Option<TokenStream> {
+            match param {
+                GenericParam::Lifetime(param) => {
+                    let lifetime = &param.lifetime;
+                    Some(parse_quote!(&#lifetime ()))
+                }
+                GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    Some(parse_quote!(#ident))
+                }
+                // unused const parameters are allowed
+                GenericParam::Const(_) => None,
+            }
+        });
+    parse_quote!(::core::marker::PhantomData<fn() -> (#(#params,)*)>)
+}
+
+/// Whether all lifetime and type parameters of `generics` appear in `types`.
+fn uses_all<'a>(generics: &Generics, types: impl Iterator<Item = &'a Type>) -> bool {
+    // lifetimes are a `'` followed by their identifier, which is collected as well
+    fn idents(tokens: TokenStream, found: &mut Vec<Ident>) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => found.push(ident),
+                TokenTree::Group(group) => idents(group.stream(), found),
+                TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+            }
+        }
+    }
+    let mut found = Vec::new();
+    for ty in types {
+        idents(parse_quote!(#ty), &mut found);
+    }
+    generics.params.iter().all(|param| match param {
+        GenericParam::Lifetime(param) => found.contains(&param.lifetime.ident),
+        GenericParam::Type(param) => found.contains(&param.ident),
+        GenericParam::Const(_) => true,
+    })
+}
+
+/// `Twin<'a, A, tail>` for struct generics `<'a, A>`
+fn twin_with(twin: &Ident, generics: &Generics, tail: Type) -> Type {
+    let params = generics.params.iter().map(|param| -> TokenStream {
+        match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                parse_quote!(#lifetime)
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                parse_quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                parse_quote!(#ident)
+            }
+        }
+    });
+    parse_quote!(#twin<#(#params,)* #tail>)
+}
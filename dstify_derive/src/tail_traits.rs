@@ -0,0 +1,105 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Ident, Path, Type, TypeParamBound, parse_quote, spanned::Spanned};
+
+/// Defines the trait named by the `dyn Trait` last field as the combination of `traits`,
+/// with a blanket impl and accessors to each of the combined trait objects.
+///
+/// `downcast_ref`/`downcast_mut` to the last field are skipped when `#[dstify(downcast)]`
+/// generates the ones returning the sized twin instead.
+pub fn derive(dst: &Dst, traits: &[Path], downcast: bool) -> Result<TokenStream, TokenStream> {
+    let combined = combined_trait(dst.tail.ty)?;
+    let vis = &dst.input.vis;
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+
+    let mut trait_fns = Vec::<TokenStream>::new();
+    let mut blanket_fns = Vec::<TokenStream>::new();
+    let mut accessors = Vec::<TokenStream>::new();
+    for path in traits {
+        let Some(last) = path.segments.last() else {
+            continue;
+        };
+        let snake = to_snake_case(&last.ident.to_string());
+        let as_ref = Ident::new(&format!("as_{snake}"), last.ident.span());
+        let as_mut = Ident::new(&format!("as_{snake}_mut"), last.ident.span());
+        trait_fns.push(parse_quote! {
+            fn #as_ref(&self) -> &(dyn #path + 'static);
+            fn #as_mut(&mut self) -> &mut (dyn #path + 'static);
+        });
+        blanket_fns.push(parse_quote! {
+            fn #as_ref(&self) -> &(dyn #path + 'static) {
+                self
+            }
+            fn #as_mut(&mut self) -> &mut (dyn #path + 'static) {
+                self
+            }
+        });
+        accessors.push(parse_quote! {
+            fn #as_ref(&self) -> &(dyn #path + 'static) {
+                #combined::#as_ref(&self.#field)
+            }
+            fn #as_mut(&mut self) -> &mut (dyn #path + 'static) {
+                #combined::#as_mut(&mut self.#field)
+            }
+        });
+        if last.ident == "Any" && !downcast {
+            accessors.push(parse_quote! {
+                fn downcast_ref<__T: ::core::any::Any>(&self) -> ::core::option::Option<&__T> {
+                    <dyn ::core::any::Any>::downcast_ref(#combined::#as_ref(&self.#field))
+                }
+                fn downcast_mut<__T: ::core::any::Any>(&mut self) -> ::core::option::Option<&mut __T> {
+                    <dyn ::core::any::Any>::downcast_mut(#combined::#as_mut(&mut self.#field))
+                }
+            });
+        }
+    }
+
+    Ok(parse_quote! {
+        #vis trait #combined: #(#traits)+* {
+            #(#trait_fns)*
+        }
+        impl<__T: #(#traits)+* + 'static> #combined for __T {
+            #(#blanket_fns)*
+        }
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    })
+}
+
+/// Name of the trait in `dyn Trait (+ AutoTrait)* (+ 'lifetime)?`
+fn combined_trait(ty: &Type) -> Result<&Ident, TokenStream> {
+    let error = |span| {
+        syn::Error::new(
+            span,
+            "`#[dstify(tail_traits(...))]` requires the last field to be `dyn Trait`, where `Trait` is the name of the generated trait",
+        )
+        .into_compile_error()
+    };
+    let Type::TraitObject(trait_object) = ty else {
+        return Err(error(ty.span()));
+    };
+    match trait_object.bounds.first() {
+        Some(TypeParamBound::Trait(bound)) => {
+            bound.path.get_ident().ok_or_else(|| error(bound.span()))
+        }
+        _ => Err(error(ty.span())),
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
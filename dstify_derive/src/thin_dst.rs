@@ -0,0 +1,81 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{LitInt, Type, parse_quote};
+
+/// Integer type storing the length of the last field for `#[dstify(max_len = ...)]`
+pub struct MaxLen {
+    pub value: LitInt,
+    pub ty: Type,
+}
+
+impl MaxLen {
+    pub fn parse(value: LitInt) -> syn::Result<Self> {
+        let ty = match value.base10_parse::<u64>()? {
+            0..=0xff => parse_quote!(u8),
+            0x100..=0xffff => parse_quote!(u16),
+            0x1_0000..=0xffff_ffff => parse_quote!(u32),
+            _ => {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "`max_len` must fit in `u32`, remove it to store the length as `usize`",
+                ));
+            }
+        };
+        Ok(Self { value, ty })
+    }
+}
+
+/// Implements `ThinDst`, storing the length of `slice` DSTs and the whole wide pointer of `dyn Trait` DSTs.
+pub fn derive(dst: &Dst, max_len: Option<&MaxLen>) -> TokenStream {
+    let input = dst.input;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    match (dst.tail.ty, max_len) {
+        (Type::TraitObject(_), _) => parse_quote! {
+            unsafe impl #impl_generics ::dstify::ThinDst for #name #ty_generics #where_clause {
+                type Metadata = *const Self;
+                const RELOCATABLE: bool = false;
+                #[inline]
+                fn metadata(ptr: *const Self) -> Self::Metadata {
+                    ptr
+                }
+                #[inline]
+                fn from_metadata(base: *mut u8, metadata: Self::Metadata) -> *mut Self {
+                    metadata.with_addr(base.addr()) as *mut Self
+                }
+            }
+        },
+        (_, None) => parse_quote! {
+            unsafe impl #impl_generics ::dstify::ThinDst for #name #ty_generics #where_clause {
+                type Metadata = usize;
+                const RELOCATABLE: bool = true;
+                #[inline]
+                fn metadata(ptr: *const Self) -> Self::Metadata {
+                    (ptr as *const [()]).len()
+                }
+                #[inline]
+                fn from_metadata(base: *mut u8, metadata: Self::Metadata) -> *mut Self {
+                    ::core::ptr::slice_from_raw_parts_mut(base, metadata) as *mut Self
+                }
+            }
+        },
+        (_, Some(MaxLen { ty, .. })) => parse_quote! {
+            unsafe impl #impl_generics ::dstify::ThinDst for #name #ty_generics #where_clause {
+                type Metadata = #ty;
+                const RELOCATABLE: bool = true;
+                #[inline]
+                fn metadata(ptr: *const Self) -> Self::Metadata {
+                    // the constructors reject longer last fields
+                    ::core::option::Option::expect(
+                        ::core::result::Result::ok(<#ty as ::core::convert::TryFrom<usize>>::try_from((ptr as *const [()]).len())),
+                        "length of the last field exceeds `max_len`",
+                    )
+                }
+                #[inline]
+                fn from_metadata(base: *mut u8, metadata: Self::Metadata) -> *mut Self {
+                    ::core::ptr::slice_from_raw_parts_mut(base, metadata as usize) as *mut Self
+                }
+            }
+        },
+    }
+}
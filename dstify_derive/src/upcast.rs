@@ -0,0 +1,35 @@
+use crate::Dst;
+use proc_macro2::TokenStream;
+use syn::{Type, parse_quote};
+
+/// `Upcast<Target>` and `From<Box<Self>> for Box<Target>`.
+/// The normal fields of both structs must be of the same types and byte orders, the last field must coerce to the target's one.
+pub fn derive(dst: &Dst, targets: &[Type]) -> TokenStream {
+    let name = &dst.input.ident;
+    let (impl_generics, ty_generics, where_clause) = dst.input.generics.split_for_impl();
+    let field = &dst.tail.member;
+    let impls = targets.iter().map(|target| -> TokenStream {
+        parse_quote! {
+            unsafe impl #impl_generics ::dstify::Upcast<#target> for #name #ty_generics #where_clause {
+                unsafe fn upcast_raw(this: *mut Self) -> *mut #target {
+                    // same normal fields in the same order, so `#[repr(C)]` places them at the same offsets,
+                    // `fn(T) -> T` is invariant, so that their lifetimes can't be changed either
+                    let _: ::core::marker::PhantomData<fn(<Self as ::dstify::private::DstFields>::Normal) -> <Self as ::dstify::private::DstFields>::Normal> =
+                        ::core::marker::PhantomData::<fn(<#target as ::dstify::private::DstFields>::Normal) -> <#target as ::dstify::private::DstFields>::Normal>;
+                    // stored in the same byte order, so that the accessors of the target read the same values
+                    let _: ::core::marker::PhantomData<<Self as ::dstify::private::DstFields>::Endian> =
+                        ::core::marker::PhantomData::<<#target as ::dstify::private::DstFields>::Endian>;
+                    let unsized_field = unsafe { &raw mut (*this).#field };
+                    let unsized_field: *mut <#target as ::dstify::private::DstFields>::Unsized = unsized_field;
+                    unsized_field.with_addr(this.addr()) as *mut #target
+                }
+            }
+            impl #impl_generics ::core::convert::From<::dstify::private::Box<#name #ty_generics>> for ::dstify::private::Box<#target> #where_clause {
+                fn from(this: ::dstify::private::Box<#name #ty_generics>) -> Self {
+                    ::dstify::Upcast::upcast_box(this)
+                }
+            }
+        }
+    });
+    parse_quote!(#(#impls)*)
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Dstify, Debug)]
+#[dstify(borrow_tail)]
+#[repr(C)]
+struct Symbol {
+    id: u32,
+    name: str,
+}
+
+#[derive(Dstify)]
+#[dstify(borrow_tail)]
+#[repr(C)]
+struct Bytes(u8, [u8]);
+
+#[test]
+fn test() {
+    let mut map = HashMap::<Box<Symbol>, u32>::new();
+    map.insert(Symbol::init_unsized(1, "foo"), 10);
+    map.insert(Symbol::init_unsized(2, "bar"), 20);
+    assert_eq!(map.get("foo"), Some(&10));
+    assert_eq!(map.get("bar"), Some(&20));
+    assert_eq!(map.get("baz"), None);
+
+    // only the tail takes part in comparisons
+    let a: Box<Symbol> = Symbol::init_unsized(1, "same");
+    let b: Box<Symbol> = Symbol::init_unsized(2, "same");
+    assert_eq!(a, b);
+    let mut set = HashSet::new();
+    assert!(set.insert(a));
+    assert!(!set.insert(b));
+    assert_eq!(set.get("same").map(|s| s.id), Some(1));
+
+    let mut set = BTreeSet::<Box<Bytes>>::new();
+    set.insert(Bytes::init_unsized(0, &[3, 4]));
+    set.insert(Bytes::init_unsized(1, &[1, 2]));
+    assert!(set.contains(&[1, 2][..]));
+    assert_eq!(set.first().map(|b| b.0), Some(1));
+}
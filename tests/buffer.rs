@@ -0,0 +1,111 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{rc::Rc, sync::Arc};
+
+#[derive(Dstify, Debug, PartialEq)]
+#[dstify(buffer = "SegmentBuf")]
+#[repr(C)]
+struct Segment {
+    id: u64,
+    name: String,
+    entries: [u32],
+}
+
+#[derive(Dstify)]
+#[dstify(buffer = "EdgesBuf")]
+#[repr(C)]
+struct Edges<'a>(&'a str, [(u16, u16)]);
+
+#[derive(Dstify)]
+#[dstify(buffer = "UnitsBuf")]
+#[repr(C)]
+struct Units([()]);
+
+#[derive(Dstify)]
+#[dstify(buffer = "SharedBuf")]
+#[repr(C)]
+struct Shared(Rc<()>, [u8]);
+
+#[test]
+fn test() {
+    let mut buf = SegmentBuf::new(1, String::from("segment"));
+    assert!(buf.is_empty());
+    assert_eq!(
+        (buf.id, buf.name.as_str(), &buf.entries),
+        (1, "segment", &[][..])
+    );
+    buf.push(1);
+    buf.extend_from_slice(&[2, 3]);
+    buf.extend([4, 5]);
+    assert_eq!(buf.len(), 5);
+    assert!(buf.capacity() >= 5);
+    buf.id = 2;
+    buf.entries[0] = 0;
+    assert_eq!(&buf.entries, &[0, 2, 3, 4, 5]);
+
+    let segment = buf.into_box();
+    assert_eq!(
+        segment,
+        Segment::init_unsized(2, String::from("segment"), &[0, 2, 3, 4, 5])
+    );
+
+    let mut buf = SegmentBuf::with_capacity(3, String::from("exact"), 2);
+    buf.extend_from_slice(&[1, 2]);
+    assert_eq!(buf.capacity(), 2);
+    let ptr = buf.entries.as_ptr();
+    let segment = buf.into_box();
+    // capacity equals length, so the allocation is reused as is
+    assert_eq!(segment.entries.as_ptr(), ptr);
+
+    let mut buf = SegmentBuf::with_capacity(4, String::new(), 100);
+    buf.push(1);
+    buf.shrink_to_fit();
+    assert_eq!(buf.capacity(), 1);
+    let segment: Arc<Segment> = buf.into_arc();
+    assert_eq!(&segment.entries, &[1]);
+
+    let mut buf = SegmentBuf::with_capacity_in::<Arc<_>>(5, String::from("shared"), 2);
+    buf.extend_from_slice(&[1, 2]);
+    let ptr = buf.entries.as_ptr();
+    let segment: Arc<Segment> = buf.into_arc();
+    // allocated like `Arc` and capacity equals length, so the allocation is reused as is
+    assert_eq!(segment.entries.as_ptr(), ptr);
+    assert_eq!((segment.id, &segment.entries), (5, &[1, 2][..]));
+
+    // growing and freezing into other pointers moves the elements
+    let mut buf = SegmentBuf::with_capacity_in::<Rc<_>>(6, String::from("moved"), 1);
+    buf.extend(1..=9);
+    let segment: Rc<Segment> = buf.into_rc();
+    assert_eq!(
+        (segment.name.as_str(), &segment.entries),
+        ("moved", &[1, 2, 3, 4, 5, 6, 7, 8, 9][..])
+    );
+    let mut buf = SegmentBuf::with_capacity_in::<Arc<_>>(7, String::from("boxed"), 0);
+    buf.push(1);
+    assert_eq!(buf.into_box().entries, [1]);
+
+    let name = String::from("graph");
+    let mut edges = EdgesBuf::new(&name);
+    edges.reserve(10);
+    assert!(edges.capacity() >= 10);
+    edges.extend((0..10).map(|i| (i, i + 1)));
+    let edges: Rc<Edges> = edges.into_rc();
+    assert_eq!((edges.0, edges.1.len(), edges.1[9]), ("graph", 10, (9, 10)));
+
+    let mut units = UnitsBuf::new();
+    units.extend([(), (), ()]);
+    assert_eq!(units.into_box().0.len(), 3);
+
+    // dropping a buffer drops the normal fields
+    let shared = Rc::new(());
+    let mut buf = SharedBuf::new(shared.clone());
+    buf.push(1);
+    assert_eq!(Rc::strong_count(&shared), 2);
+    drop(buf);
+    assert_eq!(Rc::strong_count(&shared), 1);
+    let mut buf = SharedBuf::with_capacity_in::<Rc<_>>(shared.clone(), 1);
+    buf.extend_from_slice(&[1, 2, 3]);
+    drop(buf);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
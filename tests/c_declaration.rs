@@ -0,0 +1,87 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::ffi::{CStr, c_char, c_ulong, c_void};
+
+#[derive(Dstify)]
+#[dstify(c_declaration, len_field = "len")]
+#[repr(C)]
+struct Msg {
+    len: u32,
+    kind: c_char,
+    data: [u8],
+}
+
+#[derive(Dstify)]
+#[dstify(c_declaration)]
+#[repr(C)]
+struct Matrix(usize, [[f32; 3]; 2], *mut c_void, [f64]);
+
+#[derive(Dstify)]
+#[dstify(c_declaration)]
+#[repr(C)]
+struct Names<'a> {
+    owner: *const &'a str,
+    size: c_ulong,
+    valid: bool,
+    name: CStr,
+}
+
+#[derive(Dstify)]
+#[dstify(c_declaration)]
+#[repr(C)]
+struct Text {
+    id: i64,
+    text: str,
+}
+
+#[test]
+fn test() {
+    assert_eq!(
+        Msg::C_DECLARATION,
+        "struct Msg {\n    uint32_t len;\n    char kind;\n    uint8_t data[];\n};\n"
+    );
+    assert_eq!(
+        Matrix::C_DECLARATION,
+        "struct Matrix {\n    size_t _0;\n    float _1[2][3];\n    void *_2;\n    double _3[];\n};\n"
+    );
+    assert_eq!(
+        Names::C_DECLARATION,
+        "struct Names {\n    const void *owner;\n    unsigned long size;\n    bool valid;\n    char name[];\n};\n"
+    );
+    assert_eq!(
+        Text::C_DECLARATION,
+        "struct Text {\n    int64_t id;\n    uint8_t text[];\n};\n"
+    );
+
+    let mut header = Vec::new();
+    dstify::ffi::write_header(
+        &mut header,
+        "DSTS_H",
+        &[Msg::C_DECLARATION, Text::C_DECLARATION],
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(header).unwrap(),
+        "#ifndef DSTS_H
+#define DSTS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+struct Msg {
+    uint32_t len;
+    char kind;
+    uint8_t data[];
+};
+
+struct Text {
+    int64_t id;
+    uint8_t text[];
+};
+
+#endif /* DSTS_H */
+"
+    );
+}
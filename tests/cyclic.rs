@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    fmt::Debug,
+    rc::{self, Rc},
+    sync::{self, Arc},
+};
+
+#[derive(Dstify)]
+#[dstify(cyclic)]
+#[repr(C)]
+struct Node {
+    this: rc::Weak<Node>,
+    depth: u8,
+    children: [u32],
+}
+
+#[derive(Dstify)]
+#[dstify(cyclic)]
+#[repr(C)]
+struct Shared<'a> {
+    label: &'a str,
+    this: sync::Weak<Shared<'a>>,
+    value: dyn Debug + Send + Sync,
+}
+
+#[derive(Dstify)]
+#[dstify(cyclic)]
+#[repr(C)]
+struct Empty([u64]);
+
+// `'a` is only used by the last field
+#[derive(Dstify)]
+#[dstify(cyclic)]
+#[repr(C)]
+struct Borrowing<'a> {
+    parent: rc::Weak<u32>,
+    value: dyn Debug + 'a,
+}
+
+#[test]
+fn test() {
+    let node: Rc<Node> = Node::init_unsized_cyclic(
+        |weak: &rc::Weak<Node>| {
+            assert!(weak.upgrade().is_none());
+            assert_eq!(weak.strong_count(), 0);
+            (weak.clone(), 1)
+        },
+        [1, 2],
+    );
+    let this = node.this.upgrade().unwrap();
+    assert!(Rc::ptr_eq(&this, &node));
+    drop(this);
+    assert_eq!((node.depth, &node.children), (1, &[1, 2][..]));
+    let weak = node.this.clone();
+    drop(node);
+    assert!(weak.upgrade().is_none());
+
+    let label = String::from("shared");
+    let shared: Arc<Shared> = Shared::init_unsized_cyclic(
+        |weak: &sync::Weak<Shared>| {
+            assert!(weak.upgrade().is_none());
+            (&label, weak.clone())
+        },
+        vec![1u64, 2, 3],
+    );
+    assert!(Arc::ptr_eq(&shared.this.upgrade().unwrap(), &shared));
+    assert_eq!(shared.label, "shared");
+    assert_eq!(format!("{:?}", &shared.value), "[1, 2, 3]");
+    assert_eq!(Arc::weak_count(&shared), 1);
+
+    let empty: Rc<Empty> = Empty::init_unsized_cyclic(|_: &rc::Weak<Empty>| (), []);
+    assert!(empty.0.is_empty());
+
+    let parent = Rc::new(0);
+    let value = String::from("borrowed");
+    let borrowing: Rc<Borrowing> =
+        Borrowing::init_unsized_cyclic(|_: &rc::Weak<Borrowing>| (Rc::downgrade(&parent),), &value);
+    assert!(Rc::ptr_eq(&borrowing.parent.upgrade().unwrap(), &parent));
+    assert_eq!(format!("{:?}", &borrowing.value), "\"borrowed\"");
+}
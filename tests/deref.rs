@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    ffi::{CStr, OsStr},
+    fmt::Debug,
+    path::Path,
+    rc::Rc,
+    sync::Arc,
+};
+
+#[derive(Dstify)]
+#[dstify(deref)]
+#[repr(C)]
+struct Name {
+    id: u32,
+    name: str,
+}
+
+#[derive(Dstify)]
+#[dstify(deref)]
+#[repr(C)]
+struct Blob(u8, [u8]);
+
+#[derive(Dstify)]
+#[dstify(deref)]
+#[repr(C)]
+struct Location {
+    line: u32,
+    path: Path,
+}
+
+#[derive(Dstify)]
+#[dstify(deref)]
+#[repr(C)]
+struct Symbol<'a> {
+    module: &'a str,
+    name: CStr,
+}
+
+#[derive(Dstify)]
+#[dstify(deref)]
+#[repr(C)]
+struct Dbg {
+    line: u32,
+    dbg: dyn Debug,
+}
+
+fn as_str<S: AsRef<str> + ?Sized>(s: &S) -> &str {
+    s.as_ref()
+}
+fn as_bytes<S: AsRef<[u8]> + ?Sized>(s: &S) -> &[u8] {
+    s.as_ref()
+}
+fn as_path<S: AsRef<Path> + ?Sized>(s: &S) -> &Path {
+    s.as_ref()
+}
+fn as_os_str<S: AsRef<OsStr> + ?Sized>(s: &S) -> &OsStr {
+    s.as_ref()
+}
+fn as_c_str<S: AsRef<CStr> + ?Sized>(s: &S) -> &CStr {
+    s.as_ref()
+}
+
+#[test]
+fn test() {
+    let name: Box<Name> = Name::init_unsized(1, "name");
+    assert_eq!(name.len(), 4);
+    assert_eq!(&**name, "name");
+    assert_eq!(as_str(&*name), "name");
+    assert_eq!(as_bytes(&*name), b"name");
+    assert_eq!(as_path(&*name), Path::new("name"));
+    assert_eq!(as_os_str(&*name), OsStr::new("name"));
+
+    let blob: Rc<Blob> = Blob::init_unsized(1, &[1, 2, 3]);
+    assert_eq!(blob.iter().sum::<u8>(), 6);
+    assert_eq!(as_bytes(&*blob), &[1, 2, 3]);
+
+    let location: Arc<Location> = Location::init_unsized(1, Path::new("src/lib.rs"));
+    assert_eq!(location.extension(), Some(OsStr::new("rs")));
+    assert_eq!(as_path(&*location), Path::new("src/lib.rs"));
+    assert_eq!(as_os_str(&*location), OsStr::new("src/lib.rs"));
+
+    let symbol: Box<Symbol> = Symbol::init_unsized("std", c"main");
+    assert_eq!(symbol.to_str(), Ok("main"));
+    assert_eq!(as_c_str(&*symbol), c"main");
+
+    let dbg: Box<Dbg> = Dbg::init_unsized(1, "debug");
+    assert_eq!(format!("{:?}", &**dbg), "\"debug\"");
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{any::Any, fmt::Debug};
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "EventOf", downcast)]
+#[repr(C)]
+struct Event {
+    id: u64,
+    payload: dyn Any + Send,
+}
+
+#[derive(Dstify)]
+#[dstify(tail_traits(Debug, Any), sized_twin = "DbgOf", downcast)]
+#[repr(C)]
+struct Dbg(u8, dyn DebugAny);
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "HandlerOf", downcast)]
+#[repr(C)]
+struct Handler<'a> {
+    name: &'a str,
+    handler: dyn Handle,
+}
+
+trait Handle: Any {
+    fn handle(&self) -> u32;
+}
+#[derive(Debug, PartialEq)]
+struct Const(u32);
+impl Handle for Const {
+    fn handle(&self) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn test() {
+    let event: Box<Event> = Event::init_unsized(1, String::from("payload"));
+    assert!(event.downcast_ref::<u32>().is_none());
+    let Err(event) = event.downcast::<u32>() else {
+        panic!("`u32` is not the type of the payload");
+    };
+    let Ok(mut event) = event.downcast::<String>() else {
+        panic!("`String` is the type of the payload");
+    };
+    assert_eq!(event.id, 1);
+    event.payload.push('!');
+    assert_eq!(event.payload, "payload!");
+
+    let mut event: Box<Event> = Event::init_unsized(2, 7u16);
+    event.downcast_mut::<u16>().unwrap().payload += 1;
+    assert_eq!(event.downcast_ref::<u16>().map(|e| e.payload), Some(8));
+
+    let dbg: Box<Dbg> = Dbg::init_unsized(3, vec![1u8, 2]);
+    assert_eq!(format!("{:?}", dbg.as_debug()), "[1, 2]");
+    let dbg: Box<DbgOf<Vec<u8>>> = dbg.downcast().ok().unwrap();
+    assert_eq!((dbg.0, dbg.1.as_slice()), (3, &[1, 2][..]));
+
+    let handler: Box<Handler> = Handler::init_unsized("const", Const(5));
+    assert_eq!(handler.handler.handle(), 5);
+    let handler = handler.downcast::<Const>().ok().unwrap();
+    assert_eq!((handler.name, &handler.handler), ("const", &Const(5)));
+
+    // zero-sized tail
+    let event: Box<Event> = Event::init_unsized(4, ());
+    let event: Box<EventOf<()>> = event.downcast().ok().unwrap();
+    assert_eq!(event.id, 4);
+}
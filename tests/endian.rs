@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::rc::Rc;
+
+#[derive(Dstify)]
+#[dstify(
+    endian = "be",
+    with_tail,
+    map_tail,
+    make_mut,
+    forward(Display = "{kind}/{flags}: ")
+)]
+#[repr(C)]
+struct Record {
+    kind: u32,
+    tag: [u8; 2],
+    flags: i16,
+    body: str,
+}
+
+#[derive(Dstify)]
+#[dstify(pod, len_field = "len")]
+#[repr(C)]
+struct Frame {
+    #[dstify(le)]
+    len: u16,
+    #[dstify(be)]
+    id: u16,
+    native: u32,
+    data: [u8],
+}
+
+#[derive(Dstify)]
+#[dstify(endian = "le")]
+#[repr(C)]
+struct Pair(u64, [u16]);
+
+#[test]
+fn test() {
+    let record: Box<Record> = Record::init_unsized(0x0102_0304, *b"ab", -2, "body");
+    assert_eq!(
+        (record.kind(), record.tag, record.flags()),
+        (0x0102_0304, *b"ab", -2)
+    );
+    assert_eq!(record.kind, 0x0102_0304u32.to_be());
+    assert_eq!(record.to_string(), "16909060/-2: body");
+
+    let mut other: Rc<Record> = record.with_tail("other");
+    assert_eq!(
+        (other.kind(), other.flags(), &other.body),
+        (0x0102_0304, -2, "other")
+    );
+    let shared = other.clone();
+    Record::make_mut(&mut other).set_flags(5);
+    assert_eq!(
+        (shared.flags(), other.flags(), other.kind()),
+        (-2, 5, 0x0102_0304)
+    );
+    let mapped: Box<Record> = Record::map_tail(record, |body| body.to_uppercase());
+    assert_eq!(
+        (mapped.kind(), mapped.flags(), &mapped.body),
+        (0x0102_0304, -2, "BODY")
+    );
+
+    let frame: Box<Frame> = Frame::init_unsized(0x0a0b, 7, &[1, 2, 3, 4]);
+    assert_eq!(frame.as_bytes()[..4], [4, 0, 0x0a, 0x0b]);
+    assert_eq!(frame.as_bytes()[4..8], 7u32.to_ne_bytes());
+    assert_eq!((frame.len(), frame.id(), frame.native), (4, 0x0a0b, 7));
+
+    // the bytes have the same meaning on every host
+    #[repr(C, align(4))]
+    struct Bytes([u8; 12]);
+    let mut bytes = Bytes([2, 0, 0x0c, 0x0d, 0, 0, 0, 0, 5, 6, 0, 0]);
+    let view = Frame::mut_from_bytes(&mut bytes.0[..]).unwrap();
+    assert_eq!((view.len(), view.id(), view.data.len()), (2, 0x0c0d, 4));
+    view.set_id(0x0e0f);
+    view.set_len(3);
+    assert_eq!(bytes.0[..4], [3, 0, 0x0e, 0x0f]);
+    let view = unsafe { Frame::from_c_ptr(bytes.0.as_ptr()) };
+    assert_eq!(view.data, [5, 6, 0]);
+
+    let pair: Box<Pair> = Pair::init_unsized(1, &[2]);
+    assert_eq!(
+        (pair.field_0(), pair.0, &pair.1),
+        (1, 1u64.to_le(), &[2][..])
+    );
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    error::Error,
+    fmt::Display,
+    future::Future,
+    io::{self, Read, Write},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Dstify)]
+#[dstify(forward(Debug, Display = "line {line}: ", Error))]
+#[repr(C)]
+struct Ctx {
+    line: u64,
+    err: dyn Error,
+}
+
+#[derive(Dstify)]
+#[dstify(forward(Display = "{f0}:{f1}: "))]
+#[repr(C)]
+struct Pos(u32, u32, dyn Display);
+
+#[derive(Dstify)]
+#[dstify(forward(Display = "{f}@{offsets}: "))]
+#[repr(C)]
+struct Span {
+    f: char,
+    offsets: u16,
+    text: str,
+}
+
+#[derive(Dstify)]
+#[dstify(forward(Iterator))]
+#[repr(C)]
+struct Numbers {
+    taken: usize,
+    iter: dyn Iterator<Item = u32>,
+}
+
+#[derive(Dstify)]
+#[dstify(forward(io::Read, io::Write))]
+#[repr(C)]
+struct Stream {
+    id: u8,
+    inner: dyn ReadWrite,
+}
+
+trait ReadWrite: Read + Write {}
+impl<T: Read + Write> ReadWrite for T {}
+
+#[derive(Dstify)]
+#[dstify(forward(Future))]
+#[repr(C)]
+struct Task {
+    id: u64,
+    fut: dyn Future<Output = u32>,
+}
+
+#[derive(Dstify)]
+#[dstify(forward(Display, Debug))]
+#[repr(C)]
+struct Text {
+    id: u64,
+    text: str,
+}
+
+fn assert_error<E: Error + ?Sized>(_: &E) {}
+
+#[test]
+fn test() {
+    let err = io::Error::other("oh no");
+    let ctx: Box<Ctx> = Ctx::init_unsized(42, err);
+    assert_eq!(ctx.to_string(), "line 42: oh no");
+    assert_eq!(
+        format!("{ctx:?}"),
+        format!("{:?}", io::Error::other("oh no"))
+    );
+    assert!(ctx.source().is_none());
+    assert_error(&*ctx);
+
+    let pos: Arc<Pos> = Pos::init_unsized(1, 2, "here");
+    assert_eq!(pos.to_string(), "1:2: here");
+    let span: Box<Span> = Span::init_unsized('s', 4, "text");
+    assert_eq!(span.to_string(), "s@4: text");
+
+    let mut numbers: Box<Numbers> = Numbers::init_unsized(0, [1, 2, 3].into_iter());
+    assert_eq!(numbers.size_hint(), (3, Some(3)));
+    assert_eq!(numbers.by_ref().sum::<u32>(), 6);
+    assert_eq!(numbers.next(), None);
+
+    let mut stream: Box<Stream> = Stream::init_unsized(0, io::Cursor::new(Vec::new()));
+    stream.write_all(b"hello").unwrap();
+    stream.flush().unwrap();
+    let mut out = String::new();
+    stream.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "");
+
+    let task: Box<Task> = Task::init_unsized(1, async { 7 });
+    let mut task = pin!(Box::into_pin(task));
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(task.as_mut().poll(&mut cx), Poll::Ready(7));
+
+    let text: Box<Text> = Text::init_unsized(1, "text");
+    assert_eq!(text.to_string(), "text");
+    assert_eq!(format!("{text:?}"), "\"text\"");
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, InlineDst};
+use std::{fmt::Debug, rc::Rc};
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct Msg {
+    kind: u8,
+    text: str,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Shared(Rc<()>, [u8]);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Wide(u8, [u128]);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Dynamic {
+    counter: Rc<()>,
+    value: dyn Debug,
+}
+
+#[test]
+fn test() {
+    let mut msgs: Vec<InlineDst<Msg, 48>> = vec![
+        Msg::init_unsized(1, "short"),
+        Msg::init_unsized(2, &"long".repeat(12)),
+        Msg::init_unsized(3, &"a".repeat(47)),
+        Msg::init_unsized(4, ""),
+    ];
+    let inline = msgs.iter().map(InlineDst::is_inline);
+    assert_eq!(inline.collect::<Vec<_>>(), [true, false, true, true]);
+    // moving the inline values keeps them intact
+    msgs.reverse();
+    msgs[3].text.make_ascii_uppercase();
+    msgs.shrink_to_fit();
+    let texts = msgs.iter().map(|msg| (msg.kind, msg.text.len()));
+    assert_eq!(
+        texts.collect::<Vec<_>>(),
+        [(4, 0), (3, 47), (2, 48), (1, 5)]
+    );
+    assert_eq!(
+        format!("{:?}", msgs[3]),
+        r#"Msg { kind: 1, text: "SHORT" }"#
+    );
+
+    let counter = Rc::new(());
+    let inline: InlineDst<Shared, 64> = Shared::init_unsized(counter.clone(), &[1, 2]);
+    let spilled: InlineDst<Shared, 8> = Shared::init_unsized(counter.clone(), &[1; 16]);
+    assert!(InlineDst::is_inline(&inline) && !InlineDst::is_inline(&spilled));
+    let moved = Box::new(inline);
+    assert_eq!(Rc::strong_count(&counter), 3);
+    drop((moved, spilled));
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    // alignment above `usize` spills
+    let wide: InlineDst<Wide, 64> = Wide::init_unsized(1, &[2]);
+    assert!(!InlineDst::is_inline(&wide));
+    assert_eq!(wide.1, [2]);
+
+    // `dyn Trait` DSTs always spill
+    let dynamic: InlineDst<Dynamic, 64> = Dynamic::init_unsized(counter.clone(), 1u8);
+    assert!(!InlineDst::is_inline(&dynamic));
+    assert_eq!(format!("{:?}", &dynamic.value), "1");
+    drop(dynamic);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, InitError, ThinBox};
+use std::{rc::Rc, sync::Arc};
+
+#[derive(Dstify, Debug)]
+#[dstify(len_field = "len", with_tail, map_tail, make_mut)]
+#[repr(C)]
+struct Msg {
+    kind: u16,
+    len: u32,
+    data: [u16],
+}
+
+#[derive(Dstify)]
+#[dstify(len_field = "0")]
+#[repr(C)]
+struct Short(u8, str);
+
+#[derive(Dstify)]
+#[dstify(len_field = "count", max_len = 4)]
+#[repr(C)]
+struct Bounded {
+    count: i64,
+    items: [u64],
+}
+
+/// What C would allocate for `struct msg { uint16_t kind; uint32_t len; uint16_t data[]; }`.
+#[repr(C)]
+struct CMsg<const N: usize> {
+    kind: u16,
+    len: u32,
+    data: [u16; N],
+}
+
+#[test]
+fn test() {
+    let msg: Box<Msg> = Msg::init_unsized(1, &[1, 2, 3]);
+    assert_eq!((msg.kind, msg.len, &msg.data), (1, 3, &[1, 2, 3][..]));
+
+    let other: Rc<Msg> = msg.with_tail(&[4]);
+    assert_eq!((other.kind, other.len, &other.data), (1, 1, &[4][..]));
+    let mapped: Arc<Msg> = Msg::map_tail(msg, |data| [data, data].concat());
+    assert_eq!((mapped.kind, mapped.len), (1, 6));
+
+    let mut shared = other.clone();
+    Msg::make_mut(&mut shared).kind = 2;
+    assert_eq!((other.kind, shared.kind, shared.len), (1, 2, 1));
+
+    let c_msg = CMsg {
+        kind: 7,
+        len: 3,
+        data: [1, 2, 3, 4],
+    };
+    let msg = unsafe { Msg::from_c_ptr(std::ptr::from_ref(&c_msg).cast()) };
+    assert_eq!((msg.kind, msg.len, &msg.data), (7, 3, &[1, 2, 3][..]));
+    assert_eq!(std::mem::size_of_val(msg), 16);
+
+    let short: ThinBox<Short> = Short::init_unsized("short");
+    assert_eq!((short.0, &short.1), (5, "short"));
+    let long = "x".repeat(256);
+    let err = Short::init_unsized_checked::<Box<_>>(&long).err();
+    assert_eq!(
+        err,
+        Some(InitError::TooLong {
+            len: 256,
+            max_len: 255,
+            len_field: Some("0"),
+        })
+    );
+    assert_eq!(
+        err.unwrap().to_string(),
+        "length 256 of the last field exceeds 255, the most the length field `0` can hold"
+    );
+    let view = unsafe { Short::from_c_ptr(ThinBox::as_ptr(&short).cast()) };
+    assert_eq!(&view.1, "short");
+
+    let bounded: Box<Bounded> = Bounded::init_unsized(&[1, 2]);
+    assert_eq!(bounded.count, 2);
+    assert!(matches!(
+        Bounded::init_unsized_checked::<Box<_>>(&[0; 5]),
+        Err(InitError::TooLong {
+            len: 5,
+            max_len: 4,
+            len_field: None
+        })
+    ));
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "std")]
+
+use dstify::{
+    Dstify, FromBytesError,
+    log::{Error, RecordReader, RecordWriter},
+};
+use std::io::Cursor;
+
+#[derive(Dstify, Debug)]
+#[dstify(pod, endian = "le")]
+#[repr(C)]
+struct Sample {
+    id: u64,
+    values: [u16],
+}
+
+fn ids<'a>(samples: impl IntoIterator<Item = &'a Sample>) -> Vec<(u64, Vec<u16>)> {
+    samples
+        .into_iter()
+        .map(|sample| (sample.id(), sample.values.to_vec()))
+        .collect()
+}
+
+#[test]
+fn test() {
+    let mut writer = RecordWriter::<Sample, _>::new(Vec::new());
+    for (id, values) in [(1, &[1, 2, 3][..]), (2, &[]), (3, &[4, 5, 6, 7, 8])] {
+        writer
+            .write(&Sample::init_unsized::<Box<_>>(id, values))
+            .unwrap();
+    }
+    let log = writer.into_inner();
+    // headers padded to 16, followed by 8 + 6 bytes padded to 16, 8, and 8 + 10 padded to 24
+    assert_eq!(log.len(), 16 + 16 + 16 + 8 + 16 + 24);
+    let expected = vec![(1, vec![1, 2, 3]), (2, vec![]), (3, vec![4, 5, 6, 7, 8])];
+
+    let boxed = RecordReader::<Sample, _>::new(Cursor::new(&log))
+        .collect::<Result<Vec<Box<Sample>>, _>>()
+        .unwrap();
+    assert_eq!(ids(boxed.iter().map(|sample| &**sample)), expected);
+
+    // load into an 8-aligned buffer to view the records in place
+    let mut aligned = vec![0u64; log.len() / 8];
+    for (word, bytes) in aligned.iter_mut().zip(log.chunks(8)) {
+        *word = u64::from_ne_bytes(bytes.try_into().unwrap());
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(aligned.as_ptr().cast::<u8>(), log.len()) };
+    let views = RecordReader::<Sample, _>::new(bytes)
+        .views()
+        .collect::<Result<Vec<&Sample>, _>>()
+        .unwrap();
+    assert_eq!(ids(views), expected);
+
+    let mut misaligned = vec![0u64; log.len() / 8 + 1];
+    let misaligned = unsafe {
+        std::slice::from_raw_parts_mut(misaligned.as_mut_ptr().cast::<u8>().add(4), log.len())
+    };
+    misaligned.copy_from_slice(&log);
+    assert!(matches!(
+        RecordReader::<Sample, _>::new(&*misaligned).views().next(),
+        Some(Err(Error::Record {
+            offset: 0,
+            error: FromBytesError::Misaligned { align: 8 }
+        }))
+    ));
+
+    let mut corrupted = log.clone();
+    corrupted[56 + 16 + 2] ^= 1;
+    let mut reader = RecordReader::<Sample, _>::new(&corrupted[..]);
+    assert_eq!(reader.next().unwrap().unwrap().id(), 1);
+    assert!(reader.next().unwrap().is_ok());
+    let err = reader.next().unwrap().unwrap_err();
+    assert!(matches!(err, Error::Checksum { offset: 56 }));
+    assert_eq!(err.to_string(), "checksum mismatch in the record at 56");
+    assert!(reader.next().is_none());
+
+    let truncated = &log[..log.len() - 1];
+    let records = RecordReader::<Sample, _>::new(truncated).collect::<Vec<_>>();
+    assert_eq!(records.len(), 3);
+    assert!(matches!(records[2], Err(Error::Truncated { offset: 56 })));
+    let views = RecordReader::<Sample, _>::new(&bytes[..log.len() - 1])
+        .views()
+        .collect::<Vec<_>>();
+    assert!(matches!(views[2], Err(Error::Truncated { offset: 56 })));
+
+    // a corrupted length only reads the bytes present instead of allocating for it
+    let mut corrupted = log.clone();
+    corrupted[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let records = RecordReader::<Sample, _>::new(Cursor::new(&corrupted)).collect::<Vec<_>>();
+    assert!(matches!(records[..], [Err(Error::Truncated { offset: 0 })]));
+    aligned[0] = u64::from_ne_bytes(corrupted[..8].try_into().unwrap());
+    let bytes = unsafe { std::slice::from_raw_parts(aligned.as_ptr().cast::<u8>(), log.len()) };
+    let views = RecordReader::<Sample, _>::new(bytes)
+        .views()
+        .collect::<Vec<_>>();
+    assert!(matches!(views[..], [Err(Error::Truncated { offset: 0 })]));
+
+    // the records written for `Sample` don't have the size of a `Wide` of the same length
+    #[derive(Dstify)]
+    #[dstify(pod)]
+    #[repr(C)]
+    struct Wide {
+        id: u64,
+        values: [u32],
+    }
+    let mut wide = RecordReader::<Wide, _>::new(&log[..]);
+    assert!(wide.next().unwrap().is_err());
+    assert!(wide.next().is_none());
+    let err = RecordReader::<Wide, _>::new(&log[..]).next().unwrap();
+    assert!(matches!(
+        err,
+        Err(Error::Record {
+            offset: 0,
+            error: FromBytesError::Size { len: 16 }
+        })
+    ));
+}
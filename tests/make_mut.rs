@@ -0,0 +1,67 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, ThinArc, ThinRc};
+use std::{rc::Rc, sync::Arc};
+
+#[derive(Dstify, Debug)]
+#[dstify(make_mut)]
+#[repr(C)]
+struct Config {
+    version: u64,
+    name: Rc<str>,
+    data: [u8],
+}
+
+#[derive(Dstify)]
+#[dstify(make_mut)]
+#[repr(C)]
+struct Label<'a, T: Clone> {
+    tag: &'a T,
+    text: str,
+}
+
+#[test]
+fn test() {
+    let name: Rc<str> = Rc::from("config");
+    let mut config: Rc<Config> = Config::init_unsized(1, name.clone(), &[1, 2, 3]);
+    let addr = Rc::as_ptr(&config);
+    // unique: mutated in place
+    Config::make_mut(&mut config).data[0] = 10;
+    assert_eq!(Rc::as_ptr(&config), addr);
+    assert_eq!(Rc::strong_count(&name), 2);
+
+    // shared: cloned first
+    let shared = config.clone();
+    let mutable = Config::make_mut(&mut config);
+    mutable.version += 1;
+    mutable.data[1] = 20;
+    assert!(!Rc::ptr_eq(&config, &shared));
+    assert_eq!((shared.version, &shared.data), (1, &[10, 2, 3][..]));
+    assert_eq!((config.version, &config.data), (2, &[10, 20, 3][..]));
+    assert_eq!(Rc::strong_count(&name), 3);
+    drop(shared);
+    assert_eq!(Rc::strong_count(&name), 2);
+
+    // only weak references: disassociated
+    let weak = Rc::downgrade(&config);
+    Config::make_mut(&mut config).version = 3;
+    assert!(weak.upgrade().is_none());
+    assert_eq!(config.version, 3);
+
+    let tag = 7u8;
+    let mut label: Arc<Label<u8>> = Label::init_unsized(&tag, "label");
+    let other = label.clone();
+    Label::make_mut(&mut label).text.make_ascii_uppercase();
+    assert_eq!((&other.text, &label.text), ("label", "LABEL"));
+    assert!(std::ptr::eq(label.tag, other.tag));
+
+    let mut thin: ThinRc<Label<u8>> = Label::init_unsized(&tag, "thin");
+    let other = thin.clone();
+    Label::make_mut(&mut thin).text.make_ascii_uppercase();
+    assert_eq!((&other.text, &thin.text), ("thin", "THIN"));
+
+    let mut thin: ThinArc<Config> = Config::init_unsized(1, name, &[]);
+    let other = thin.clone();
+    Config::make_mut(&mut thin).version = 2;
+    assert_eq!((other.version, thin.version), (1, 2));
+}
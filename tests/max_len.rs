@@ -0,0 +1,112 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, InitError, ThinArc, ThinBox, ThinDst, ThinRc};
+use std::ffi::CStr;
+
+#[derive(Dstify, Debug)]
+#[dstify(max_len = 255)]
+#[repr(C)]
+struct Short {
+    text: str,
+}
+
+#[derive(Dstify)]
+#[dstify(max_len = 1000)]
+#[repr(C)]
+struct Medium(u8, [u16]);
+
+#[derive(Dstify)]
+#[dstify(max_len = 70000)]
+#[repr(C)]
+struct Long<'a, A: Copy>(&'a str, [A]);
+
+#[derive(Dstify)]
+#[dstify(max_len = 4)]
+#[repr(C)]
+struct Name(CStr);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Unbounded([u8]);
+
+#[derive(Dstify)]
+#[dstify(max_len = 4, resize_tail)]
+#[repr(C)]
+struct Resizable(u8, [u8]);
+
+fn assert_metadata<T: ?Sized + ThinDst, M>() {
+    assert_eq!(size_of::<T::Metadata>(), size_of::<M>());
+}
+
+#[test]
+fn test() {
+    assert_metadata::<Short, u8>();
+    assert_metadata::<Medium, u16>();
+    assert_metadata::<Long<'_, ()>, u32>();
+    assert_metadata::<Unbounded, usize>();
+
+    let short: ThinBox<Short> = Short::init_unsized("a".repeat(255).as_str());
+    assert_eq!(short.text.len(), 255);
+    let short: ThinRc<Short> = Short::init_unsized("short");
+    assert_eq!(&short.text, "short");
+    let err = Short::init_unsized_checked::<ThinBox<_>>("a".repeat(256).as_str()).unwrap_err();
+    assert_eq!(
+        err,
+        InitError::TooLong {
+            len: 256,
+            max_len: 255,
+            len_field: None,
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "length 256 of the last field exceeds `max_len` 255"
+    );
+    let result = std::panic::catch_unwind(|| {
+        let _: Box<Short> = Short::init_unsized("a".repeat(300).as_str());
+    });
+    assert!(result.is_err());
+
+    let medium: ThinArc<Medium> = Medium::init_unsized_checked(7, &[1; 1000]).unwrap();
+    assert_eq!((medium.0, medium.1.len()), (7, 1000));
+    assert!(Medium::init_unsized_checked::<ThinArc<_>>(7, &[1; 1001]).is_err());
+
+    let long: ThinBox<Long<'_, u8>> = Long::init_unsized("long", &[3; 70000]);
+    assert_eq!((long.0, long.1.len()), ("long", 70000));
+    assert!(Long::init_unsized_checked::<Box<_>>("long", &[3u8; 70001]).is_err());
+
+    // the nul terminator counts towards the length
+    let name: ThinBox<Name> = Name::init_unsized(c"abc");
+    assert_eq!(&name.0, c"abc");
+    assert!(Name::init_unsized_checked::<Box<_>>(c"abcd").is_err());
+
+    let unbounded: Result<ThinBox<Unbounded>, std::alloc::LayoutError> =
+        Unbounded::init_unsized_checked(&[1; 300]);
+    assert_eq!(unbounded.unwrap().0.len(), 300);
+
+    // growing past `max_len` fails before reallocating and leaves the last field as it was
+    let mut resizable: Box<Resizable> = Resizable::init_unsized(1, &[1, 2]);
+    Resizable::extend_tail(&mut resizable, &[3]).unwrap();
+    assert_eq!(
+        Resizable::extend_tail(&mut resizable, &[4, 5]),
+        Err(InitError::TooLong {
+            len: 5,
+            max_len: 4,
+            len_field: None,
+        })
+    );
+    assert_eq!(
+        Resizable::resize_tail_with(&mut resizable, 6, || 0),
+        Err(InitError::TooLong {
+            len: 6,
+            max_len: 4,
+            len_field: None,
+        })
+    );
+    assert_eq!(resizable.1, [1, 2, 3]);
+    Resizable::resize_tail_with(&mut resizable, 4, || 0).unwrap();
+    Resizable::truncate_tail(&mut resizable, 2);
+    assert_eq!(resizable.1, [1, 2]);
+    let thin: ThinBox<Resizable> = Resizable::init_unsized(resizable.0, &resizable.1);
+    assert_eq!(thin.1, [1, 2]);
+}
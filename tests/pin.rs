@@ -0,0 +1,169 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, SmartPointer};
+use std::{
+    alloc::Layout,
+    future::Future,
+    marker::PhantomPinned,
+    ops::Deref,
+    pin::Pin,
+    ptr,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// Remembers its address when first pinned and checks that it never changes.
+struct Anchor {
+    addr: AtomicUsize,
+    _pinned: PhantomPinned,
+}
+
+impl Anchor {
+    fn new() -> Self {
+        Self {
+            addr: AtomicUsize::new(0),
+            _pinned: PhantomPinned,
+        }
+    }
+}
+
+trait Check {
+    fn check(self: Pin<&Self>) -> usize;
+}
+
+impl Check for Anchor {
+    fn check(self: Pin<&Self>) -> usize {
+        let addr = std::ptr::from_ref(&*self).addr();
+        let pinned_at =
+            match self
+                .addr
+                .compare_exchange(0, addr, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => addr,
+                Err(pinned_at) => pinned_at,
+            };
+        assert_eq!(pinned_at, addr, "moved after pinning");
+        addr
+    }
+}
+
+impl Future for Anchor {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<usize> {
+        let first = self.addr.load(Ordering::Relaxed) == 0;
+        let addr = self.as_ref().check();
+        if first {
+            Poll::Pending
+        } else {
+            Poll::Ready(addr)
+        }
+    }
+}
+
+/// Initializes an `Anchor` in the allocation of `P`, remembering the address it was initialized at.
+fn init_in_place<P>() -> (P, usize)
+where
+    P: SmartPointer<Anchor> + Deref<Target = Anchor>,
+{
+    let mut addr = 0;
+    let anchor = unsafe {
+        P::new(Layout::new::<Anchor>(), |base| {
+            let base = base.cast::<Anchor>();
+            base.write(Anchor::new());
+            addr = ptr::addr_of!(*base).addr();
+            (*base).addr.store(addr, Ordering::Relaxed);
+            base
+        })
+    };
+    (anchor, addr)
+}
+
+fn check_in_place<P>()
+where
+    P: SmartPointer<Anchor> + Deref<Target = Anchor>,
+{
+    let (anchor, addr) = init_in_place::<P>();
+    assert_eq!(
+        ptr::from_ref(&*anchor).addr(),
+        addr,
+        "moved after initialization"
+    );
+    // SAFETY: `P` is pinned
+    unsafe { Pin::new_unchecked(&*anchor) }.check();
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Task {
+    id: u32,
+    future: dyn Future<Output = usize>,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Checked {
+    id: u32,
+    anchor: dyn Check + Send + Sync,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Pinned {
+    pinned: PhantomPinned,
+    data: [u8],
+}
+
+fn future(task: Pin<&mut Task>) -> Pin<&mut dyn Future<Output = usize>> {
+    // SAFETY: `future` is structurally pinned
+    unsafe { task.map_unchecked_mut(|task| &mut task.future) }
+}
+
+fn anchor(checked: Pin<&Checked>) -> Pin<&(dyn Check + Send + Sync)> {
+    // SAFETY: `anchor` is structurally pinned
+    unsafe { checked.map_unchecked(|checked| &checked.anchor) }
+}
+
+#[test]
+fn test() {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    check_in_place::<Pin<Box<Anchor>>>();
+    check_in_place::<Pin<Rc<Anchor>>>();
+    check_in_place::<Pin<Arc<Anchor>>>();
+
+    let mut task: Pin<Box<Task>> = Task::init_unsized(1, Anchor::new());
+    assert!(future(task.as_mut()).poll(&mut cx).is_pending());
+    let mut tasks = vec![task];
+    tasks.insert(0, Task::init_unsized(2, Anchor::new()));
+    let mut task = tasks.pop().unwrap();
+    assert_eq!(task.id, 1);
+    let addr = std::ptr::from_ref(&task.future).addr();
+    assert_eq!(future(task.as_mut()).poll(&mut cx), Poll::Ready(addr));
+
+    let checked: Pin<Rc<Checked>> = Checked::init_unsized(2, Anchor::new());
+    let addr = anchor(checked.as_ref()).check();
+    let clone = checked.clone();
+    drop(checked);
+    assert_eq!(anchor(clone.as_ref()).check(), addr);
+    assert_eq!(clone.id, 2);
+
+    let checked: Pin<Arc<Checked>> = Checked::init_unsized(3, Anchor::new());
+    let addr = anchor(checked.as_ref()).check();
+    let checked = std::thread::spawn(move || {
+        assert_eq!(anchor(checked.as_ref()).check(), addr);
+        checked
+    })
+    .join()
+    .unwrap();
+    assert_eq!(anchor(checked.as_ref()).check(), addr);
+
+    let pinned: Pin<Box<Pinned>> = Pinned::init_unsized(PhantomPinned, &[1, 2, 3]);
+    let pinned: Pin<Box<Pinned>> =
+        Pinned::init_unsized_checked(PhantomPinned, &pinned.data).unwrap();
+    assert_eq!(pinned.data, [1, 2, 3]);
+}
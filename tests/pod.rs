@@ -0,0 +1,175 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, FromBytesError, Pod, PodDst, ThinBox};
+use std::rc::Rc;
+
+#[derive(Dstify, Debug)]
+#[dstify(pod)]
+#[repr(C)]
+struct Packet {
+    kind: u32,
+    flags: u32,
+    body: [u8],
+}
+
+#[derive(Dstify)]
+#[dstify(pod)]
+#[repr(C)]
+struct Samples<T: Pod> {
+    rate: [u16; 2],
+    samples: [T],
+}
+
+#[derive(Dstify)]
+#[dstify(pod)]
+#[repr(C)]
+struct Words([u64]);
+
+#[derive(Dstify)]
+#[dstify(pod, resize_tail, buffer = "RgbBuf", sized_twin = "RgbTwin")]
+#[repr(C)]
+struct Rgb {
+    id: u64,
+    pixels: [[u8; 3]],
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+unsafe impl Pod for Point {}
+
+#[derive(Dstify)]
+#[dstify(pod)]
+#[repr(C)]
+struct Path {
+    origin: Point,
+    points: [Point],
+}
+
+#[derive(Dstify)]
+#[dstify(pod, max_len = 3)]
+#[repr(C)]
+struct Bounded([u8]);
+
+#[repr(C, align(8))]
+struct Aligned<const N: usize>([u8; N]);
+
+#[test]
+fn test() {
+    let mut buf = Aligned([0; 32]);
+    let bytes = &mut buf.0;
+    bytes[..4].copy_from_slice(&7u32.to_ne_bytes());
+    bytes[8..12].copy_from_slice(b"body");
+
+    let packet = Packet::ref_from_bytes(&bytes[..12]).unwrap();
+    assert_eq!(
+        (packet.kind, packet.flags, &packet.body),
+        (7, 0, &b"body"[..])
+    );
+    let packet = Packet::ref_from_bytes(&bytes[..]).unwrap();
+    assert_eq!(packet.body.len(), 24);
+    assert_eq!(std::mem::size_of_val(packet), 32);
+
+    let packet = Packet::mut_from_bytes(&mut bytes[..16]).unwrap();
+    packet.flags = 3;
+    packet.body[4] = b'!';
+    assert_eq!(
+        bytes[4..13],
+        [3, 0, 0, 0, b'b', b'o', b'd', b'y', b'!'].map(u8::from)[..]
+    );
+
+    // trailing padding of `Packet` can't be covered by 13 bytes
+    assert_eq!(
+        Packet::ref_from_bytes(&bytes[..13]).err(),
+        Some(FromBytesError::Size { len: 13 })
+    );
+    assert_eq!(
+        Packet::ref_from_bytes(&bytes[..7]).err(),
+        Some(FromBytesError::Size { len: 7 })
+    );
+    assert_eq!(
+        Packet::ref_from_bytes(&bytes[1..13]).err(),
+        Some(FromBytesError::Misaligned { align: 4 })
+    );
+    assert_eq!(Packet::ref_from_bytes(&bytes[..8]).unwrap().body.len(), 0);
+
+    let samples = Samples::<i16>::ref_from_bytes(&bytes[..10]).unwrap();
+    assert_eq!(samples.samples.len(), 3);
+    assert_eq!(
+        Samples::<u32>::ref_from_bytes(&bytes[..10]).err(),
+        Some(FromBytesError::Size { len: 10 })
+    );
+
+    let words = Words::mut_from_bytes(&mut bytes[8..]).unwrap();
+    assert_eq!(words.0.len(), 3);
+    words.0[2] = u64::MAX;
+    assert_eq!(bytes[24..], [0xff; 8]);
+    assert_eq!(Words::ref_from_bytes(&bytes[..0]).unwrap().0.len(), 0);
+
+    let path = Path::ref_from_bytes(&buf.0[..24]).unwrap();
+    assert_eq!((path.origin.x, path.points.len()), (7, 2));
+    assert_eq!(path.points[0].x, i32::from_ne_bytes(*b"body"));
+
+    // trailing padding is zeroed by every constructor
+    let rgb: Box<Rgb> = Rgb::init_unsized(1, &[[1, 2, 3], [4, 5, 6]]);
+    let bytes = rgb.as_bytes();
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(bytes[8..], [1, 2, 3, 4, 5, 6, 0, 0]);
+
+    let mut padded = [0xaa; 17];
+    padded[1..15].copy_from_slice(&bytes[..14]);
+    let copy: Rc<Rgb> = Rgb::from_bytes(&padded[1..]).unwrap();
+    assert_eq!((copy.id, &copy.pixels), (1, &[[1, 2, 3], [4, 5, 6]][..]));
+    assert_eq!(copy.as_bytes(), bytes);
+    let copy: ThinBox<Rgb> = Rgb::from_bytes(bytes).unwrap();
+    assert_eq!(copy.as_bytes(), bytes);
+    assert_eq!(
+        Rgb::from_bytes::<Box<_>>(&padded[1..15]).err(),
+        Some(FromBytesError::Size { len: 14 })
+    );
+
+    let mut resized: Box<Rgb> = Rgb::init_unsized(1, &[[1, 2, 3]]);
+    Rgb::extend_tail(&mut resized, &[[4, 5, 6], [7, 8, 9]]);
+    Rgb::truncate_tail(&mut resized, 2);
+    assert_eq!(resized.as_bytes(), bytes);
+
+    let mut buf = RgbBuf::new(1);
+    buf.push([1, 2, 3]);
+    buf.push([4, 5, 6]);
+    assert_eq!(buf.into_box().as_bytes(), bytes);
+
+    let twin: Box<Rgb> = Box::new(RgbTwin {
+        id: 1,
+        pixels: [[1, 2, 3], [4, 5, 6]],
+    })
+    .into();
+    assert_eq!(twin.as_bytes(), bytes);
+
+    let err = FromBytesError::Misaligned { align: 4 };
+    assert_eq!(err.to_string(), "bytes are not aligned to 4");
+
+    // the length is checked against `max_len` before allocating
+    let bounded: ThinBox<Bounded> = Bounded::from_bytes(b"abc").unwrap();
+    assert_eq!(&bounded.0, b"abc");
+    let too_long = FromBytesError::TooLong {
+        len: 10,
+        max_len: 3,
+    };
+    assert_eq!(
+        Bounded::from_bytes::<ThinBox<_>>(&[0; 10]).err(),
+        Some(too_long)
+    );
+    assert_eq!(
+        <Bounded as PodDst>::from_bytes_with_len::<Box<_>>(&[0; 4], 4).err(),
+        Some(FromBytesError::TooLong { len: 4, max_len: 3 })
+    );
+    assert_eq!(Bounded::ref_from_bytes(&[0; 10]).err(), Some(too_long));
+    assert_eq!(
+        too_long.to_string(),
+        "length 10 of the last field exceeds `max_len` 3"
+    );
+}
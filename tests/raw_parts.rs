@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{ffi::CStr, ptr::NonNull, rc::Rc};
+
+#[derive(Dstify, Debug)]
+#[dstify(raw_parts)]
+#[repr(C)]
+struct Message {
+    id: u32,
+    payload: [u16],
+}
+
+#[derive(Dstify)]
+#[dstify(raw_parts)]
+#[repr(C)]
+struct Named {
+    counter: Rc<()>,
+    name: str,
+}
+
+#[derive(Dstify)]
+#[dstify(max_len = 255, raw_parts)]
+#[repr(C)]
+struct Short(u8, CStr);
+
+/// Opaque handle as it would be passed to C.
+#[repr(C)]
+struct Handle {
+    ptr: *mut u8,
+    len: usize,
+}
+
+extern "C" fn message_free(handle: Handle) -> u32 {
+    let ptr = NonNull::new(handle.ptr).unwrap();
+    let message = unsafe { Message::from_raw_parts(ptr, handle.len) };
+    message.payload.iter().map(|&x| u32::from(x)).sum::<u32>() + message.id
+}
+
+#[test]
+fn test() {
+    let message: Box<Message> = Message::init_unsized(10, &[1, 2, 3]);
+    let payload = message.payload.as_ptr();
+    let (ptr, len) = Message::into_raw_parts(message);
+    assert_eq!(
+        ptr.as_ptr().cast::<u16>().wrapping_add(2),
+        payload.cast_mut()
+    );
+    assert_eq!(len, 3);
+    let handle = Handle {
+        ptr: ptr.as_ptr(),
+        len,
+    };
+    assert_eq!(message_free(handle), 16);
+
+    let counter = Rc::new(());
+    let named: Box<Named> = Named::init_unsized(counter.clone(), "named");
+    let (ptr, len) = Named::into_raw_parts(named);
+    assert_eq!(len, 5);
+    let named = unsafe { Named::from_raw_parts(ptr, len) };
+    assert_eq!(&named.name, "named");
+    drop(named);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    let short: Box<Short> = Short::init_unsized(1, c"short");
+    let (ptr, len) = Short::into_raw_parts(short);
+    let short = unsafe { Short::from_raw_parts(ptr, len) };
+    assert_eq!(&short.1, c"short");
+
+    // `NonNull` returned directly, freed by `dealloc`
+    let named: NonNull<Named> = Named::init_unsized(counter.clone(), "raw");
+    assert_eq!(Rc::strong_count(&counter), 2);
+    assert_eq!(unsafe { &named.as_ref().name }, "raw");
+    unsafe { dstify::dealloc(named) };
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    let empty: NonNull<Message> = Message::init_unsized_checked(0, &[]).unwrap();
+    let message = unsafe { Box::from_raw(empty.as_ptr()) };
+    assert!(message.payload.is_empty());
+
+    // without `raw_parts`, methods of the same names can be defined
+    #[derive(Dstify)]
+    #[repr(C)]
+    struct Zst([()]);
+    impl Zst {
+        fn from_raw_parts(len: usize) -> Box<Self> {
+            Self::init_unsized(&vec![(); len])
+        }
+    }
+    assert_eq!(Zst::from_raw_parts(2).0.len(), 2);
+    let zst: NonNull<Zst> = Zst::init_unsized(&[(); 4]);
+    unsafe { dstify::dealloc(zst) };
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    fmt::Display,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+#[derive(Dstify)]
+#[dstify(with_tail, map_tail)]
+#[repr(C)]
+struct Record {
+    id: u64,
+    name: String,
+    data: str,
+}
+
+#[derive(Dstify)]
+#[dstify(with_tail, map_tail)]
+#[repr(C)]
+struct Bytes(u8, [u8]);
+
+#[derive(Dstify)]
+#[dstify(map_tail)]
+#[repr(C)]
+struct Handler {
+    counter: Counted,
+    handler: dyn Display,
+}
+
+#[derive(Dstify)]
+#[dstify(with_tail)]
+#[repr(C)]
+struct Labeled {
+    line: u32,
+    label: dyn Display,
+}
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted(u32);
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct Noisy(&'static str);
+impl Display for Noisy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(100, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test() {
+    let record: Box<Record> = Record::init_unsized(1, String::from("name"), "data");
+    let copy: Rc<Record> = record.with_tail("other data");
+    assert_eq!(
+        (copy.id, copy.name.as_str(), &copy.data),
+        (1, "name", "other data")
+    );
+    let upper: Arc<Record> = Record::map_tail(record, |data| data.to_uppercase());
+    assert_eq!(
+        (upper.id, upper.name.as_str(), &upper.data),
+        (1, "name", "DATA")
+    );
+
+    let bytes: Box<Bytes> = Bytes::init_unsized(3, &[1, 2, 3]);
+    let longer: Box<Bytes> = Bytes::map_tail(bytes, |data| [data, data].concat());
+    assert_eq!((longer.0, &longer.1), (3, &[1, 2, 3, 1, 2, 3][..]));
+    let empty: Box<Bytes> = longer.with_tail(&[]);
+    assert_eq!((empty.0, &empty.1), (3, &[][..]));
+
+    let handler: Box<Handler> = Handler::init_unsized(Counted(7), Noisy("old"));
+    let handler: Box<Handler> = Handler::map_tail(handler, |old| format!("{old} -> new"));
+    // the old last field is dropped, the normal field is moved
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 100);
+    assert_eq!(
+        (handler.counter.0, handler.handler.to_string()),
+        (7, String::from("old -> new"))
+    );
+    drop(handler);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 101);
+
+    let labeled: Box<Labeled> = Labeled::init_unsized(1, "label");
+    let relabeled: Box<Labeled> = labeled.with_tail(2);
+    assert_eq!(
+        (relabeled.line, relabeled.label.to_string()),
+        (1, String::from("2"))
+    );
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+#[derive(Dstify, Debug, PartialEq)]
+#[dstify(resize_tail)]
+#[repr(C)]
+struct Rec {
+    id: u32,
+    data: [u16],
+}
+
+#[derive(Dstify)]
+#[dstify(resize_tail)]
+#[repr(C)]
+struct Zst((), [()]);
+
+#[test]
+fn test() {
+    let mut rec: Box<Rec> = Rec::init_unsized(1, &[]);
+    Rec::extend_tail(&mut rec, &[1, 2, 3]);
+    assert_eq!(*rec, *Rec::init_unsized::<Box<_>>(1, &[1, 2, 3]));
+    Rec::extend_tail(&mut rec, &[4]);
+    assert_eq!(&rec.data, &[1, 2, 3, 4]);
+    Rec::truncate_tail(&mut rec, 10);
+    assert_eq!(&rec.data, &[1, 2, 3, 4]);
+    Rec::truncate_tail(&mut rec, 1);
+    assert_eq!(&rec.data, &[1]);
+    Rec::resize_tail_with(&mut rec, 3, || 7);
+    assert_eq!(&rec.data, &[1, 7, 7]);
+    Rec::resize_tail_with(&mut rec, 0, || 7);
+    assert_eq!((rec.id, &rec.data), (1, &[][..]));
+    assert_eq!(size_of_val(&*rec), size_of::<u32>());
+
+    let mut zst: Box<Zst> = Zst::init_unsized((), &[()]);
+    Zst::extend_tail(&mut zst, &[(), ()]);
+    assert_eq!(zst.1.len(), 3);
+    Zst::truncate_tail(&mut zst, 0);
+    assert_eq!(zst.1.len(), 0);
+
+    // a panicking producer leaves the already produced elements in place
+    let mut produced = 0;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Rec::resize_tail_with(&mut rec, 5, || {
+            produced += 1;
+            if produced == 3 {
+                panic!("producer failed");
+            }
+            produced
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!((rec.id, &rec.data), (1, &[1, 2][..]));
+    Rec::extend_tail(&mut rec, &[3]);
+    assert_eq!(&rec.data, &[1, 2, 3]);
+}
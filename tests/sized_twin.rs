@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
+
+#[derive(Dstify, Debug, PartialEq)]
+#[dstify(sized_twin = "NumbersOf")]
+#[repr(C)]
+struct Numbers {
+    id: u8,
+    numbers: [u64],
+}
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "PairOf")]
+#[repr(C)]
+struct Pair<'a, A: Copy>(&'a A, u16, [A]);
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "LabelOf")]
+#[repr(C)]
+struct Label {
+    line: u32,
+    label: dyn Display + Send,
+}
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "TextOf")]
+#[repr(C)]
+struct Text {
+    id: u8,
+    text: str,
+}
+
+// `'a` is only used by the last field
+#[derive(Dstify)]
+#[dstify(sized_twin = "CtxOf")]
+#[repr(C)]
+struct Ctx<'a> {
+    id: u8,
+    value: dyn Debug + 'a,
+}
+
+#[derive(Dstify)]
+#[dstify(sized_twin = "ElemsOf")]
+#[repr(C)]
+struct Elems<'a, A>(u8, [&'a A]);
+
+const NUMBERS: NumbersOf<[u64; 3]> = NumbersOf {
+    id: 1,
+    numbers: [1, 2, 3],
+};
+
+#[test]
+fn test() {
+    let numbers: Box<Numbers> = Box::new(NUMBERS).into();
+    assert_eq!(numbers.id, 1);
+    assert_eq!(&numbers.numbers, &[1, 2, 3]);
+    assert_eq!(numbers, Numbers::init_unsized(1, &[1, 2, 3]));
+    assert_eq!(size_of_val(&*numbers), size_of::<NumbersOf<[u64; 3]>>());
+
+    let empty: Box<Numbers> = Box::new(NumbersOf { id: 0, numbers: [] }).into();
+    assert!(empty.numbers.is_empty());
+
+    let pair: Box<Pair<char>> = Box::new(PairOf(&'a', 2, ['b', 'c'])).into();
+    assert_eq!((*pair.0, pair.1, &pair.2), ('a', 2, &['b', 'c'][..]));
+
+    let label: Box<Label> = Box::new(LabelOf {
+        line: 10,
+        label: String::from("label"),
+    })
+    .into();
+    assert_eq!(
+        (label.line, label.label.to_string()),
+        (10, String::from("label"))
+    );
+
+    let text = TextOf { id: 3, text: "any" };
+    assert_eq!((text.id, text.text), (3, "any"));
+    let _: Box<Text> = Text::init_unsized(text.id, text.text);
+
+    let value = String::from("borrowed");
+    let ctx: Box<Ctx> = Box::new(CtxOf {
+        id: 4,
+        value: &value,
+        _marker: PhantomData,
+    })
+    .into();
+    assert_eq!(
+        (ctx.id, format!("{:?}", &ctx.value)),
+        (4, "\"borrowed\"".into())
+    );
+
+    let elems: Box<Elems<char>> = Box::new(ElemsOf(5, [&'x'], PhantomData)).into();
+    assert_eq!((elems.0, *elems.1[0]), (5, 'x'));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+
+use dstify::Dstify;
+use std::{
+    any::Any,
+    fmt::{self, Debug},
+    rc::Rc,
+};
+
+#[derive(Dstify)]
+#[dstify(tail_traits(Debug, fmt::Display, Any))]
+#[repr(C)]
+struct Value {
+    id: u32,
+    value: dyn DebugDisplayAny,
+}
+
+#[derive(Dstify)]
+#[dstify(tail_traits(Iterator<Item = u8>, Debug))]
+#[repr(C)]
+struct Bytes(u8, dyn BytesIter + Send);
+
+#[test]
+fn test() {
+    let value: Box<Value> = Value::init_unsized(1, 42u64);
+    assert_eq!(format!("{:?}", value.as_debug()), "42");
+    assert_eq!(value.as_display().to_string(), "42");
+    assert_eq!(value.downcast_ref::<u64>(), Some(&42));
+    assert_eq!(value.downcast_ref::<u32>(), None);
+
+    let mut value: Box<Value> = Value::init_unsized(2, String::from("text"));
+    value.downcast_mut::<String>().unwrap().push('!');
+    assert_eq!(value.as_display().to_string(), "text!");
+    assert_eq!(value.as_any().type_id(), std::any::TypeId::of::<String>());
+
+    let value: Rc<Value> = Value::init_unsized(3, 'c');
+    assert_eq!(format!("{}", value.as_display()), "c");
+
+    let mut bytes: Box<Bytes> = Bytes::init_unsized(0, b"abc".iter().copied());
+    assert_eq!(bytes.as_iterator_mut().next(), Some(b'a'));
+    assert_eq!(bytes.as_iterator_mut().collect::<Vec<_>>(), b"bc");
+    assert!(format!("{:?}", bytes.as_debug()).starts_with("Copied"));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, ThinArc, ThinRc};
+use std::{
+    fmt::Debug,
+    sync::{Arc, atomic::AtomicU8},
+    thread,
+};
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct Interned {
+    hash: u64,
+    text: str,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Samples(Arc<()>, [u16]);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Task {
+    id: u32,
+    state: dyn Debug + Send + Sync,
+}
+
+#[repr(align(32))]
+#[derive(Debug)]
+struct Aligned(#[allow(dead_code)] u8);
+
+#[test]
+fn test() {
+    assert_eq!(size_of::<ThinRc<Interned>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinArc<Task>>>(), size_of::<usize>());
+
+    // slice tails
+    let mut interned: ThinRc<Interned> = Interned::init_unsized(42, "interned");
+    ThinRc::get_mut(&mut interned)
+        .unwrap()
+        .text
+        .make_ascii_uppercase();
+    let other = interned.clone();
+    assert!(ThinRc::ptr_eq(&interned, &other));
+    assert_eq!((other.hash, &other.text), (42, "INTERNED"));
+    assert_eq!(ThinRc::strong_count(&interned), 2);
+    assert!(ThinRc::get_mut(&mut interned).is_none());
+    drop(other);
+
+    let weak = ThinRc::downgrade(&interned);
+    assert_eq!(ThinRc::weak_count(&interned), 1);
+    assert!(ThinRc::get_mut(&mut interned).is_none());
+    let upgraded = weak.upgrade().unwrap();
+    assert!(ThinRc::ptr_eq(&upgraded, &interned));
+    drop((upgraded, interned));
+    assert_eq!(weak.strong_count(), 0);
+    assert!(weak.upgrade().is_none());
+    drop(weak);
+
+    let counter = Arc::new(());
+    let samples: ThinArc<Samples> = Samples::init_unsized(counter.clone(), &[1, 2, 3]);
+    let clones = vec![samples.clone(); 8];
+    assert_eq!(Arc::strong_count(&counter), 2);
+    assert_eq!(ThinArc::strong_count(&samples), 9);
+    let weak = ThinArc::downgrade(&samples);
+    drop((samples, clones));
+    assert_eq!(Arc::strong_count(&counter), 1);
+    assert!(weak.upgrade().is_none());
+
+    // `dyn` tails
+    let tasks: Vec<ThinArc<Task>> = vec![
+        Task::init_unsized(1, AtomicU8::new(1)),
+        Task::init_unsized(2, Aligned(2)),
+        Task::init_unsized(3, ()),
+    ];
+    let aligned = ThinArc::as_ptr(&tasks[1]);
+    assert_eq!(aligned.cast::<u8>() as usize % 32, 0);
+    let handles = (0..4).map(|_| {
+        let tasks = tasks.clone();
+        thread::spawn(move || {
+            for _ in 0..100 {
+                let weak = tasks.iter().map(ThinArc::downgrade).collect::<Vec<_>>();
+                let strong = weak.iter().map(|weak| weak.upgrade().unwrap());
+                let debug = strong.map(|task| format!("{}: {:?}", task.id, &task.state));
+                assert_eq!(
+                    debug.collect::<Vec<_>>(),
+                    ["1: 1", "2: Aligned(2)", "3: ()"]
+                );
+            }
+        })
+    });
+    for handle in handles.collect::<Vec<_>>() {
+        handle.join().unwrap();
+    }
+    let mut tasks = tasks;
+    for task in &mut tasks {
+        assert_eq!(ThinArc::weak_count(task), 0);
+        assert!(ThinArc::get_mut(task).is_some());
+    }
+
+    let checked: Result<ThinArc<Interned>, _> = Interned::init_unsized_checked(1, "checked");
+    assert_eq!(&checked.unwrap().text, "checked");
+}
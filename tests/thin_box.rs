@@ -0,0 +1,66 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, ThinBox};
+use std::{fmt::Debug, rc::Rc};
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct Named {
+    id: u16,
+    name: str,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Values(u8, [u64]);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Units([()]);
+
+#[repr(align(64))]
+#[derive(Debug)]
+struct Aligned(#[allow(dead_code)] u8);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Dynamic {
+    counter: Rc<()>,
+    value: dyn Debug,
+}
+
+#[test]
+fn test() {
+    assert_eq!(size_of::<ThinBox<Named>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinBox<Dynamic>>>(), size_of::<usize>());
+
+    let mut named: ThinBox<Named> = Named::init_unsized(7, "seven");
+    assert_eq!((named.id, &named.name), (7, "seven"));
+    named.id += 1;
+    named.name.make_ascii_uppercase();
+    assert_eq!(format!("{named:?}"), r#"Named { id: 8, name: "SEVEN" }"#);
+
+    let values: ThinBox<Values> = Values::init_unsized(1, &[2, 3, 4]);
+    assert_eq!((values.0, &values.1), (1, &[2, 3, 4][..]));
+    let empty: ThinBox<Values> = Values::init_unsized(1, &[]);
+    assert!(empty.1.is_empty());
+    let units: ThinBox<Units> = Units::init_unsized(&[(), ()]);
+    assert_eq!(units.0.len(), 2);
+
+    let counter = Rc::new(());
+    let boxes: Vec<ThinBox<Dynamic>> = vec![
+        Dynamic::init_unsized(counter.clone(), 1u8),
+        Dynamic::init_unsized(counter.clone(), "two"),
+        Dynamic::init_unsized(counter.clone(), Aligned(3)),
+    ];
+    assert_eq!(Rc::strong_count(&counter), 4);
+    let debug = boxes.iter().map(|b| format!("{:?}", &b.value));
+    assert_eq!(debug.collect::<Vec<_>>(), ["1", "\"two\"", "Aligned(3)"]);
+    let aligned = ThinBox::as_ptr(&boxes[2]);
+    assert_eq!(aligned.cast::<u8>() as usize % 64, 0);
+    drop(boxes);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    let checked: Result<ThinBox<Named>, _> = Named::init_unsized_checked(1, "checked");
+    assert_eq!(&checked.unwrap().name, "checked");
+}
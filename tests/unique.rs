@@ -0,0 +1,95 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, UniqueArc, UniqueRc};
+use std::{
+    fmt::{Debug, Write},
+    rc::Rc,
+    sync::Arc,
+};
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct Record {
+    checksum: u32,
+    data: [u8],
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Aligned(u128, [u8]);
+
+#[repr(align(64))]
+struct Page(#[allow(dead_code)] u8);
+
+#[repr(align(65536))]
+struct Huge(#[allow(dead_code)] u8);
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Log {
+    counter: Rc<()>,
+    sink: dyn Write,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Empty([()]);
+
+fn checksum(data: &[u8]) -> u32 {
+    data.iter().map(|&byte| u32::from(byte)).sum()
+}
+
+#[test]
+fn test() {
+    let mut record: UniqueArc<Record> = Record::init_unsized(0, &[1, 2, 3]);
+    record.data[0] = 10;
+    record.checksum = checksum(&record.data);
+    let data_ptr = record.data.as_ptr();
+    let record: Arc<Record> = record.into();
+    assert_eq!((record.checksum, &record.data), (15, &[10, 2, 3][..]));
+    assert_eq!(record.data.as_ptr(), data_ptr);
+    let clone = record.clone();
+    drop(record);
+    assert_eq!(Arc::strong_count(&clone), 1);
+
+    let mut aligned: UniqueRc<Aligned> = Aligned::init_unsized(1, &[2; 17]);
+    aligned.0 += 1;
+    let aligned = UniqueRc::into_rc(aligned);
+    assert_eq!((aligned.0, aligned.1.len()), (2, 17));
+
+    let counter = Rc::new(());
+    let mut log: UniqueRc<Log> = Log::init_unsized(counter.clone(), String::new());
+    write!(&mut log.sink, "hello").unwrap();
+    let log: Rc<Log> = UniqueRc::into_rc(log);
+    assert_eq!(Rc::strong_count(&counter), 2);
+    drop(log);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    // dropped without converting
+    let unique: UniqueRc<Log> = Log::init_unsized(counter.clone(), String::new());
+    drop(unique);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    #[derive(Dstify)]
+    #[repr(C)]
+    struct Paged {
+        id: u8,
+        page: dyn Send,
+    }
+    let paged: UniqueArc<Paged> = Paged::init_unsized(1, Page(2));
+    let paged = UniqueArc::into_arc(paged);
+    assert_eq!(paged.id, 1);
+    assert_eq!(std::ptr::from_ref(&paged.page).addr() % 64, 0);
+
+    // alignments above the page size are supported as well
+    let huge: UniqueRc<Paged> = Paged::init_unsized(3, Huge(4));
+    let huge = UniqueRc::into_rc(huge);
+    assert_eq!(huge.id, 3);
+    assert_eq!(std::ptr::from_ref(&huge.page).addr() % 65536, 0);
+    let huge: UniqueArc<Paged> = Paged::init_unsized(5, Huge(6));
+    assert_eq!(std::ptr::from_ref(&huge.page).addr() % 65536, 0);
+
+    let empty: UniqueArc<Empty> = Empty::init_unsized(&[]);
+    let empty: Arc<Empty> = empty.into();
+    assert!(empty.0.is_empty());
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+
+use dstify::{Dstify, Upcast};
+use std::{fmt::Debug, rc::Rc, sync::Arc};
+
+trait Task: Debug {
+    fn run(&self) -> u32;
+}
+#[derive(Debug)]
+struct Const(u32);
+impl Task for Const {
+    fn run(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Dstify)]
+#[dstify(upcast_to(AnyJob, Opaque))]
+#[repr(C)]
+struct Job {
+    id: u64,
+    task: dyn Task + Send,
+}
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct AnyJob {
+    id: u64,
+    task: dyn Debug,
+}
+
+#[derive(Dstify, Debug)]
+#[repr(C)]
+struct Opaque(u64, dyn Debug + Send);
+
+#[derive(Dstify)]
+#[dstify(upcast_to(Tagged<'a>))]
+#[repr(C)]
+struct Named<'a> {
+    tag: u8,
+    name: &'a str,
+    value: dyn Task,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct Tagged<'a>(u8, &'a str, dyn Debug);
+
+// the byte orders match, whether given for the struct or the field
+#[derive(Dstify)]
+#[dstify(endian = "be", upcast_to(BigAny))]
+#[repr(C)]
+struct BigJob {
+    id: u32,
+    task: dyn Task,
+}
+
+#[derive(Dstify)]
+#[repr(C)]
+struct BigAny {
+    #[dstify(be)]
+    id: u32,
+    task: dyn Debug,
+}
+
+#[test]
+fn test() {
+    let job: Box<Job> = Job::init_unsized(1, Const(7));
+    assert_eq!(job.task.run(), 7);
+    let any: Box<AnyJob> = job.into();
+    assert_eq!(any.id, 1);
+    assert_eq!(format!("{:?}", &any.task), "Const(7)");
+
+    let job: Rc<Job> = Job::init_unsized(2, Const(8));
+    let any: Rc<AnyJob> = Job::upcast_rc(job);
+    assert_eq!(
+        (any.id, format!("{:?}", &any.task)),
+        (2, String::from("Const(8)"))
+    );
+
+    let job: Arc<Job> = Job::init_unsized(3, Const(9));
+    let opaque: Arc<Opaque> = Job::upcast_arc(job.clone());
+    assert_eq!(
+        (opaque.0, format!("{:?}", &opaque.1)),
+        (3, String::from("Const(9)"))
+    );
+    drop(opaque);
+    assert_eq!(Arc::strong_count(&job), 1);
+
+    let mut job: Box<Job> = Job::init_unsized(4, Const(10));
+    Upcast::<AnyJob>::upcast_mut(&mut *job).id = 5;
+    let any: &AnyJob = job.upcast_ref();
+    assert_eq!(any.id, 5);
+
+    let name = String::from("name");
+    let named: Box<Named> = Named::init_unsized(6, &name, Const(11));
+    let tagged: Box<Tagged> = named.into();
+    assert_eq!((tagged.0, tagged.1), (6, "name"));
+    assert_eq!(format!("{:?}", &tagged.2), "Const(11)");
+
+    let job: Box<BigJob> = BigJob::init_unsized(0x0102_0304, Const(12));
+    let any: Box<BigAny> = job.into();
+    assert_eq!((any.id(), any.id), (0x0102_0304, 0x0102_0304u32.to_be()));
+}